@@ -0,0 +1,89 @@
+//! Serves a mock Live Client Data API for offline development.
+//!
+//! Usage: rumble-mock [--port PORT] [--fixtures DIR] [--speed SPEED]
+//!                    [--loading SECONDS] [--spectator] [--cert-out FILE]
+
+use std::env;
+use std::fs;
+use std::net::SocketAddr;
+use std::process;
+use std::time::Duration;
+
+use rumble::live::mock::{MockGame, MockServer};
+
+const USAGE: &str = "usage: rumble-mock [--port PORT] [--fixtures DIR] [--speed SPEED] \
+                     [--loading SECONDS] [--spectator] [--cert-out FILE]";
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2);
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let mut port: u16 = 2999;
+    let mut fixtures = None;
+    let mut speed = None;
+    let mut loading = None;
+    let mut spectator = false;
+    let mut cert_out = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| fail(&format!("missing value for {}", arg)))
+        };
+        match arg.as_str() {
+            "--port" => port = value().parse().unwrap_or_else(|_| fail("invalid port")),
+            "--fixtures" => fixtures = Some(value()),
+            "--speed" => speed = Some(value().parse().unwrap_or_else(|_| fail("invalid speed"))),
+            "--loading" => {
+                let seconds = value()
+                    .parse()
+                    .unwrap_or_else(|_| fail("invalid loading time"));
+                loading = Some(Duration::from_secs_f64(seconds));
+            }
+            "--spectator" => spectator = true,
+            "--cert-out" => cert_out = Some(value()),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            other => fail(&format!("unknown argument {}", other)),
+        }
+    }
+
+    // flags apply on top of the fixtures, whatever their order
+    let mut game = match fixtures {
+        Some(dir) => MockGame::from_dir(&dir)
+            .unwrap_or_else(|e| fail(&format!("could not load {}: {}", dir, e))),
+        None => MockGame::new(),
+    };
+    if let Some(speed) = speed {
+        game = game.with_speed(speed);
+    }
+    if let Some(loading) = loading {
+        game = game.with_loading_time(loading);
+    }
+    if spectator {
+        game = game.spectator(true);
+    }
+
+    let server = MockServer::bind(SocketAddr::from(([127, 0, 0, 1], port)), game)
+        .await
+        .unwrap_or_else(|e| fail(&format!("could not start server: {}", e)));
+    if let Some(path) = cert_out {
+        fs::write(&path, server.ca_pem())
+            .unwrap_or_else(|e| fail(&format!("could not write {}: {}", path, e)));
+    }
+    let fingerprint: Vec<String> = server
+        .certificate_sha256()
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect();
+    println!("serving {}", server.base_url());
+    println!("certificate sha256 {}", fingerprint.join(":"));
+
+    std::future::pending::<()>().await;
+}
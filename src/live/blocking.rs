@@ -0,0 +1,431 @@
+//! A synchronous client with the same endpoints as the async one, for programs
+//! without an async runtime.
+//!
+//! Events are polled with [`EventCursor::poll_blocking`](crate::live::events::EventCursor::poll_blocking),
+//! there is no blocking counterpart of the async event stream.
+
+use std::thread;
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use reqwest::Method;
+
+use crate::live::config::LiveClientConfig;
+use crate::live::error::{LiveClientError, LiveClientResult};
+use crate::live::models::*;
+use crate::live::request;
+use crate::live::response::ResponseInfo;
+use crate::live::retry::{Attempt, Attempts, RetryPolicy};
+use crate::live::sequence::Sequence;
+use crate::live::state::GameState;
+use crate::live::transport::{TransportRequest, TransportResponse};
+
+pub struct LiveClient {
+    config: LiveClientConfig,
+    client: Client,
+}
+
+impl LiveClient {
+    /// Builds a client, panicking if the certificate or http client cannot be set up.
+    /// Use [`LiveClient::try_new`] to handle those errors instead.
+    pub fn new(config: impl Into<LiveClientConfig>) -> Self {
+        Self::try_new(config).expect("Failed to build LiveClient.")
+    }
+
+    pub fn try_new(config: impl Into<LiveClientConfig>) -> LiveClientResult<Self> {
+        let config = config.into();
+        let builder = config.configure(Client::builder())?;
+        let client = builder
+            .build()
+            .map_err(|e| LiveClientError::from_reqwest(e, 0))?;
+        Ok(Self { config, client })
+    }
+
+    pub fn request(&self, method: Method, path: &str) -> TransportRequest {
+        TransportRequest::new(method, path)
+    }
+
+    pub fn execute<T: serde::de::DeserializeOwned>(
+        &self,
+        request: TransportRequest,
+    ) -> LiveClientResult<T> {
+        let rinfo = self.execute_raw(request)?;
+        LiveClientError::decode(&rinfo.response.body, rinfo.retries)
+    }
+
+    pub fn execute_raw(&self, request: TransportRequest) -> LiveClientResult<ResponseInfo> {
+        self.execute_raw_with_policy(request, &self.config.retry_policy)
+    }
+
+    fn execute_raw_with_policy(
+        &self,
+        request: TransportRequest,
+        policy: &RetryPolicy,
+    ) -> LiveClientResult<ResponseInfo> {
+        let mut attempts = Attempts::new(policy);
+        loop {
+            match attempts.check(self.send(&request)) {
+                Attempt::Done(result) => break result,
+                Attempt::RetryAfter(delay) => thread::sleep(delay),
+            }
+        }
+    }
+
+    /// Sends a request once, without retries or checking the status.
+    pub fn send(&self, request: &TransportRequest) -> LiveClientResult<TransportResponse> {
+        let response = request
+            .build(&self.config.base_url, |method, url| {
+                self.client.request(method, url)
+            })
+            .send()
+            .map_err(|e| LiveClientError::from_reqwest(e, 0))?;
+        TransportResponse::read(response.status(), response.bytes())
+    }
+
+    pub fn player_api(&self) -> PlayerApi<'_> {
+        PlayerApi {
+            client: self,
+            timeout: None,
+        }
+    }
+
+    pub fn game_api(&self) -> GameApi<'_> {
+        GameApi {
+            client: self,
+            timeout: None,
+        }
+    }
+
+    /// The Replay API, available while a replay or spectated game is open.
+    pub fn replay_api(&self) -> ReplayApi<'_> {
+        ReplayApi {
+            client: self,
+            timeout: None,
+        }
+    }
+
+    /// Classifies the local game client into a [`GameState`], without retrying.
+    pub fn game_state(&self) -> LiveClientResult<GameState> {
+        let response = self.execute_raw_with_policy(request::game_events(), &RetryPolicy::none());
+        GameState::from_response(response)
+    }
+
+    /// Waits until a game is in progress, checking every `interval`.
+    pub fn wait_for_game(&self, interval: Duration) -> LiveClientResult<()> {
+        while self.game_state()? != GameState::InProgress {
+            thread::sleep(interval);
+        }
+        Ok(())
+    }
+}
+
+pub struct PlayerApi<'a> {
+    client: &'a LiveClient,
+    timeout: Option<Duration>,
+}
+
+impl<'a> PlayerApi<'a> {
+    /// Overrides the configured request timeout for calls made through this api.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn get_player_score(&self, player: impl Into<PlayerId>) -> LiveClientResult<PlayerScore> {
+        let request = request::player_score(&player.into()).with_optional_timeout(self.timeout);
+        self.client.execute::<PlayerScore>(request)
+    }
+
+    pub fn get_player_items(
+        &self,
+        player: impl Into<PlayerId>,
+    ) -> LiveClientResult<Vec<PlayerItem>> {
+        let request = request::player_items(&player.into()).with_optional_timeout(self.timeout);
+        self.client.execute::<Vec<PlayerItem>>(request)
+    }
+
+    pub fn get_player_summoner_spells(
+        &self,
+        player: impl Into<PlayerId>,
+    ) -> LiveClientResult<SummonerSpells> {
+        let request =
+            request::player_summoner_spells(&player.into()).with_optional_timeout(self.timeout);
+        self.client.execute::<SummonerSpells>(request)
+    }
+
+    pub fn get_player_main_runes(
+        &self,
+        player: impl Into<PlayerId>,
+    ) -> LiveClientResult<MainRunes> {
+        let request =
+            request::player_main_runes(&player.into()).with_optional_timeout(self.timeout);
+        self.client.execute::<MainRunes>(request)
+    }
+
+    pub fn get_player_list(&self) -> LiveClientResult<Vec<Player>> {
+        let request = request::player_list().with_optional_timeout(self.timeout);
+        self.client.execute::<Vec<Player>>(request)
+    }
+
+    pub fn get_active_player_abilities(&self) -> LiveClientResult<ActivePlayerAbilities> {
+        let request = request::active_player_abilities().with_optional_timeout(self.timeout);
+        self.client.execute::<ActivePlayerAbilities>(request)
+    }
+
+    pub fn get_active_player_runes(&self) -> LiveClientResult<ActivePlayerRunes> {
+        let request = request::active_player_runes().with_optional_timeout(self.timeout);
+        self.client.execute::<ActivePlayerRunes>(request)
+    }
+
+    pub fn get_active_player(&self) -> LiveClientResult<ActivePlayer> {
+        let request = request::active_player().with_optional_timeout(self.timeout);
+        self.client.execute::<ActivePlayer>(request)
+    }
+
+    pub fn get_active_player_name(&self) -> LiveClientResult<String> {
+        let request = request::active_player_name().with_optional_timeout(self.timeout);
+        self.client.execute::<String>(request)
+    }
+}
+
+pub struct GameApi<'a> {
+    client: &'a LiveClient,
+    timeout: Option<Duration>,
+}
+
+impl<'a> GameApi<'a> {
+    /// Overrides the configured request timeout for calls made through this api.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn get_all_game_data(&self) -> LiveClientResult<AllGameData> {
+        let request = request::all_game_data().with_optional_timeout(self.timeout);
+        self.client.execute::<AllGameData>(request)
+    }
+
+    pub fn get_game_stats(&self) -> LiveClientResult<GameStats> {
+        let request = request::game_stats().with_optional_timeout(self.timeout);
+        self.client.execute::<GameStats>(request)
+    }
+
+    pub fn get_game_events(&self) -> LiveClientResult<GameEventAll> {
+        let request = request::game_events().with_optional_timeout(self.timeout);
+        self.client.execute::<GameEventAll>(request)
+    }
+
+    /// Fetches only the events that happened after `event_id`.
+    pub fn get_game_events_since(&self, event_id: i32) -> LiveClientResult<GameEventAll> {
+        let request = request::game_events_since(event_id).with_optional_timeout(self.timeout);
+        self.client.execute::<GameEventAll>(request)
+    }
+}
+
+pub struct ReplayApi<'a> {
+    client: &'a LiveClient,
+    timeout: Option<Duration>,
+}
+
+impl<'a> ReplayApi<'a> {
+    /// Overrides the configured request timeout for calls made through this api.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn get_game(&self) -> LiveClientResult<ReplayGame> {
+        let request = request::replay_game().with_optional_timeout(self.timeout);
+        self.client.execute::<ReplayGame>(request)
+    }
+
+    pub fn get_playback(&self) -> LiveClientResult<PlaybackState> {
+        let request = request::playback().with_optional_timeout(self.timeout);
+        self.client.execute::<PlaybackState>(request)
+    }
+
+    /// Applies `update` and returns the resulting playback state.
+    pub fn set_playback(&self, update: &PlaybackUpdate) -> LiveClientResult<PlaybackState> {
+        let request = request::set_playback(update)?.with_optional_timeout(self.timeout);
+        self.client.execute::<PlaybackState>(request)
+    }
+
+    pub fn pause(&self) -> LiveClientResult<PlaybackState> {
+        self.set_playback(&PlaybackUpdate::new().with_paused(true))
+    }
+
+    pub fn play(&self) -> LiveClientResult<PlaybackState> {
+        self.set_playback(&PlaybackUpdate::new().with_paused(false))
+    }
+
+    /// Sets the playback speed, `1.0` is real time.
+    pub fn set_speed(&self, speed: f64) -> LiveClientResult<PlaybackState> {
+        self.set_playback(&PlaybackUpdate::new().with_speed(speed))
+    }
+
+    /// Jumps to `time` seconds into the game.
+    pub fn seek(&self, time: f64) -> LiveClientResult<PlaybackState> {
+        self.set_playback(&PlaybackUpdate::new().with_time(time))
+    }
+
+    pub fn get_render(&self) -> LiveClientResult<RenderSettings> {
+        let request = request::render().with_optional_timeout(self.timeout);
+        self.client.execute::<RenderSettings>(request)
+    }
+
+    /// Sends the fields of `update` and returns the resulting render settings.
+    pub fn update_render(&self, update: &RenderUpdate) -> LiveClientResult<RenderSettings> {
+        if update.is_empty() {
+            return self.get_render();
+        }
+        let request = request::update_render(update)?.with_optional_timeout(self.timeout);
+        self.client.execute::<RenderSettings>(request)
+    }
+
+    pub fn get_sequence(&self) -> LiveClientResult<Sequence> {
+        let request = request::sequence().with_optional_timeout(self.timeout);
+        self.client.execute::<Sequence>(request)
+    }
+
+    /// Validates `sequence` and starts playing it, replacing the current sequence.
+    pub fn set_sequence(&self, sequence: &Sequence) -> LiveClientResult<Sequence> {
+        sequence.validate()?;
+        let request = request::set_sequence(sequence)?.with_optional_timeout(self.timeout);
+        self.client.execute::<Sequence>(request)
+    }
+
+    /// Stops the current sequence by posting an empty one.
+    pub fn clear_sequence(&self) -> LiveClientResult<Sequence> {
+        self.set_sequence(&Sequence::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::events::EventCursor;
+    use crate::live::mock::{MockGame, MockServer};
+    use crate::live::sequence::Keyframe;
+    use std::sync::mpsc;
+    use tokio::sync::oneshot;
+
+    #[test]
+    fn not_running() {
+        let config = LiveClientConfig::builder()
+            .base_url("https://127.0.0.1:1")
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+        let lcu = LiveClient::new(config);
+        let err = lcu.game_api().get_game_stats().unwrap_err();
+        assert!(err.is_connect());
+    }
+
+    #[test]
+    fn retries() {
+        let mut policy = RetryPolicy::new();
+        policy.max_retries = 2;
+        policy.initial_backoff = Duration::ZERO;
+        let config = LiveClientConfig::builder()
+            .base_url("https://127.0.0.1:1")
+            .retry_policy(policy)
+            .build()
+            .unwrap();
+        let lcu = LiveClient::new(config);
+        let request = lcu
+            .request(Method::POST, "/replay/playback")
+            .json(&PlaybackUpdate::new().with_paused(true))
+            .unwrap();
+
+        let err = lcu.execute_raw(request).err().unwrap();
+        assert!(err.is_connect());
+        assert_eq!(err.retries(), 2);
+    }
+
+    /// Runs `test` against a mock served from another thread, blocking calls must not
+    /// run on the runtime serving the mock.
+    fn with_mock(game: MockGame, test: impl FnOnce(LiveClient)) {
+        let (config_tx, config_rx) = mpsc::channel();
+        let (stop_tx, stop_rx) = oneshot::channel::<()>();
+        let server = thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                let server = MockServer::start(game).await.unwrap();
+                config_tx.send(server.config()).unwrap();
+                stop_rx.await.ok();
+                server.shutdown().await;
+            });
+        });
+        test(LiveClient::new(config_rx.recv().unwrap()));
+
+        stop_tx.send(()).unwrap();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn mock_server() {
+        with_mock(MockGame::new(), |lcu| {
+            assert!(lcu.game_api().get_game_stats().is_ok());
+            let score = lcu.player_api().get_player_score("Lux Bot").unwrap();
+            assert_eq!(score.kills, 0);
+        });
+    }
+
+    #[test]
+    fn wait_for_game() {
+        let game = MockGame::new().with_loading_time(Duration::from_millis(200));
+        with_mock(game, |lcu| {
+            assert_eq!(lcu.game_state().unwrap(), GameState::Loading);
+            lcu.wait_for_game(Duration::from_millis(20)).unwrap();
+            assert_eq!(lcu.game_state().unwrap(), GameState::InProgress);
+        });
+    }
+
+    #[test]
+    fn poll_events() {
+        with_mock(MockGame::new().with_speed(0.0), |lcu| {
+            let mut cursor = EventCursor::new();
+            let events = cursor.poll_blocking(&lcu.game_api()).unwrap();
+            assert_eq!(events[0].event_id, 0);
+            assert!(cursor.poll_blocking(&lcu.game_api()).unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn replay_api() {
+        with_mock(MockGame::new(), |lcu| {
+            // the mock serves no replay
+            let err = lcu.replay_api().get_playback().unwrap_err();
+            assert_eq!(err.status(), Some(reqwest::StatusCode::NOT_FOUND));
+
+            let invalid = Sequence {
+                field_of_view: vec![Keyframe::new(0.0, -1.0, Easing::Linear)],
+                ..Sequence::default()
+            };
+            let err = lcu.replay_api().set_sequence(&invalid).unwrap_err();
+            assert!(matches!(err, LiveClientError::InvalidRequest { .. }));
+        });
+    }
+
+    #[test]
+    fn timeout() {
+        // the listener never accepts, so the tls handshake hangs until the timeout
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = LiveClientConfig::builder()
+            .base_url(format!("https://{}", listener.local_addr().unwrap()))
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+        let lcu = LiveClient::new(config);
+
+        let err = lcu
+            .game_api()
+            .with_timeout(Duration::from_millis(100))
+            .get_game_stats()
+            .unwrap_err();
+        assert!(err.is_timeout());
+    }
+}
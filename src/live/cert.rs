@@ -1,7 +1,17 @@
 use reqwest::Certificate;
-use std::fs::File;
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{CertificateError, ClientConfig, ServerName};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fmt;
+use std::fs;
 use std::io;
-use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::live::config::HttpClientBuilder;
+use crate::live::error::{LiveClientError, LiveClientResult};
 
 // this is riot games official API certificate, you can verify it here: https://static.developer.riotgames.com/docs/lol/riotgames.pem
 const RIOT_GAMES_CERT: [u8; 1492] = [
@@ -75,17 +85,247 @@ const RIOT_GAMES_CERT: [u8; 1492] = [
     45, 45, 69, 78, 68, 32, 67, 69, 82, 84, 73, 70, 73, 67, 65, 84, 69, 45, 45, 45, 45, 45,
 ];
 
-pub fn get_certificate() -> Certificate {
-    return Certificate::from_pem(RIOT_GAMES_CERT.as_ref()).unwrap();
+/// Where the client gets the certificate it trusts for the game client.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub enum CertificateSource {
+    /// The Riot Games certificate bundled with the crate.
+    #[default]
+    Embedded,
+    /// A PEM file on disk.
+    File(PathBuf),
+    /// PEM encoded certificate bytes.
+    Pem(Vec<u8>),
+    /// The root certificates of the platform, e.g. behind a proxy with a public certificate.
+    SystemRoots,
+    /// Accepts only a server certificate whose DER encoding has this SHA-256 digest.
+    Sha256Pin([u8; 32]),
+    /// Accepts any certificate. Only meant for tests against local mocks.
+    InsecureForTests,
+}
+
+impl CertificateSource {
+    /// Parses a SHA-256 fingerprint, with or without `:` separators.
+    pub fn sha256_pin(fingerprint: &str) -> LiveClientResult<Self> {
+        let hex: String = fingerprint.chars().filter(|c| *c != ':').collect();
+        let invalid = || LiveClientError::Certificate {
+            message: format!("invalid SHA-256 fingerprint `{}`", fingerprint),
+        };
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+        let mut pin = [0u8; 32];
+        for (i, byte) in pin.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+        Ok(CertificateSource::Sha256Pin(pin))
+    }
+
+    /// Configures the client builder to trust this source.
+    ///
+    /// Every source goes through rustls. Sources bringing their own certificates trust
+    /// only those, not the platform roots.
+    pub(crate) fn apply<B: HttpClientBuilder>(&self, builder: B) -> LiveClientResult<B> {
+        let builder = builder.use_rustls_tls();
+        let builder = match self {
+            CertificateSource::Embedded => builder
+                .tls_built_in_root_certs(false)
+                .add_root_certificate(get_certificate()?),
+            CertificateSource::File(path) => load_certificates(path)?.into_iter().fold(
+                builder.tls_built_in_root_certs(false),
+                B::add_root_certificate,
+            ),
+            CertificateSource::Pem(pem) => parse_certificates(pem)?.into_iter().fold(
+                builder.tls_built_in_root_certs(false),
+                B::add_root_certificate,
+            ),
+            CertificateSource::SystemRoots => builder.tls_built_in_root_certs(true),
+            // the pin replaces chain validation and is checked during the handshake
+            CertificateSource::Sha256Pin(pin) => {
+                builder.use_preconfigured_tls(PinVerifier::client_config(*pin))
+            }
+            CertificateSource::InsecureForTests => builder.danger_accept_invalid_certs(true),
+        };
+        Ok(builder)
+    }
+}
+
+impl fmt::Debug for CertificateSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CertificateSource::Embedded => write!(f, "Embedded"),
+            CertificateSource::File(path) => f.debug_tuple("File").field(path).finish(),
+            CertificateSource::Pem(pem) => write!(f, "Pem({} bytes)", pem.len()),
+            CertificateSource::SystemRoots => write!(f, "SystemRoots"),
+            CertificateSource::Sha256Pin(pin) => {
+                let hex: Vec<String> = pin.iter().map(|b| format!("{:02X}", b)).collect();
+                write!(f, "Sha256Pin({})", hex.join(":"))
+            }
+            CertificateSource::InsecureForTests => write!(f, "InsecureForTests"),
+        }
+    }
+}
+
+pub fn get_certificate() -> LiveClientResult<Certificate> {
+    Certificate::from_pem(RIOT_GAMES_CERT.as_ref()).map_err(|e| LiveClientError::Certificate {
+        message: format!("invalid PEM certificate: {}", e),
+    })
+}
+
+/// Loads every certificate of a PEM file.
+pub fn load_certificates(path: &Path) -> LiveClientResult<Vec<Certificate>> {
+    let pem = fs::read(path).map_err(|e| LiveClientError::Certificate {
+        message: format!("could not read {}: {}", path.display(), e),
+    })?;
+    parse_certificates(&pem).map_err(|e| LiveClientError::Certificate {
+        message: format!("{} in {}", e, path.display()),
+    })
+}
+
+/// Parses the certificates of a PEM bundle, skipping any text around them.
+fn parse_certificates(pem: &[u8]) -> LiveClientResult<Vec<Certificate>> {
+    let invalid = |message: String| LiveClientError::Certificate { message };
+    let ders = rustls_pemfile::certs(&mut &*pem)
+        .map_err(|e| invalid(format!("invalid PEM certificate: {}", e)))?;
+    if ders.is_empty() {
+        return Err(invalid("no PEM certificate found".into()));
+    }
+    ders.iter()
+        .map(|der| {
+            Certificate::from_der(der)
+                .map_err(|e| invalid(format!("invalid PEM certificate: {}", e)))
+        })
+        .collect()
+}
+
+/// Accepts exactly the server certificate whose DER encoding hashes to the pin.
+struct PinVerifier {
+    pin: [u8; 32],
+}
+
+impl PinVerifier {
+    fn client_config(pin: [u8; 32]) -> ClientConfig {
+        ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(PinVerifier { pin }))
+            .with_no_client_auth()
+    }
 }
 
-//TODO rewrite
-pub fn load_certificate() -> Option<Certificate> {
-    let cert = {
-        let mut cert_file = File::open("./riotgames.pem").ok()?;
-        let mut cert_raw = Vec::new();
-        cert_file.read_to_end(&mut cert_raw).ok()?;
-        Certificate::from_pem(&cert_raw).ok()?
-    };
-    Some(cert)
+impl ServerCertVerifier for PinVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if Sha256::digest(&end_entity.0).as_slice() == self.pin {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::InvalidCertificate(CertificateError::Other(
+                Arc::new(PinMismatch),
+            )))
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PinMismatch;
+
+impl fmt::Display for PinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "server certificate does not match the pinned fingerprint"
+        )
+    }
+}
+
+impl Error for PinMismatch {}
+
+/// Finds a handshake rejected by a [`CertificateSource::Sha256Pin`] among the causes of
+/// a failed request.
+pub(crate) fn pin_mismatch(error: &(dyn Error + 'static)) -> Option<String> {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if let Some(rustls::Error::InvalidCertificate(CertificateError::Other(e))) =
+            error.downcast_ref::<rustls::Error>()
+        {
+            if e.is::<PinMismatch>() {
+                return Some(e.to_string());
+            }
+        }
+        // io errors do not list the error they wrap as their source
+        source = match error.downcast_ref::<io::Error>() {
+            Some(e) => e.get_ref().map(|e| e as &(dyn Error + 'static)),
+            None => error.source(),
+        };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::ClientBuilder;
+
+    #[test]
+    fn embedded() {
+        assert!(get_certificate().is_ok());
+        assert!(CertificateSource::Embedded
+            .apply(ClientBuilder::new())
+            .is_ok());
+    }
+
+    #[test]
+    fn file() {
+        let source = CertificateSource::File(PathBuf::from("./riotgames.pem"));
+        assert!(source.apply(ClientBuilder::new()).is_ok());
+
+        let source = CertificateSource::File(PathBuf::from("./missing.pem"));
+        assert!(matches!(
+            source.apply(ClientBuilder::new()),
+            Err(LiveClientError::Certificate { .. })
+        ));
+    }
+
+    #[test]
+    fn pem() {
+        let source = CertificateSource::Pem(RIOT_GAMES_CERT.to_vec());
+        assert!(source.apply(ClientBuilder::new()).is_ok());
+
+        let source = CertificateSource::Pem(b"not a certificate".to_vec());
+        assert!(source.apply(ClientBuilder::new()).is_err());
+
+        // bundles exported by other tools carry comments and bag attributes
+        let mut pem = b"# Riot Games root\nBag Attributes\n    friendlyName: riot\n".to_vec();
+        pem.extend_from_slice(&RIOT_GAMES_CERT);
+        let source = CertificateSource::Pem(pem);
+        assert!(source.apply(ClientBuilder::new()).is_ok());
+    }
+
+    #[test]
+    fn system_roots() {
+        let builder = CertificateSource::SystemRoots
+            .apply(ClientBuilder::new())
+            .unwrap();
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn sha256_pin() {
+        let fingerprint = "00:11:22:33:44:55:66:77:88:99:AA:BB:CC:DD:EE:FF:\
+                           00:11:22:33:44:55:66:77:88:99:aa:bb:cc:dd:ee:ff";
+        match CertificateSource::sha256_pin(fingerprint).unwrap() {
+            CertificateSource::Sha256Pin(pin) => {
+                assert_eq!(pin[1], 0x11);
+                assert_eq!(pin[31], 0xff);
+            }
+            _ => panic!("Did not match Sha256Pin"),
+        }
+        assert!(CertificateSource::sha256_pin("00:11").is_err());
+        assert!(CertificateSource::sha256_pin(&"zz".repeat(32)).is_err());
+    }
 }
@@ -1,63 +1,93 @@
-use reqwest::{Client, Method, RequestBuilder};
+use reqwest::Method;
 
 use crate::live::{
     config::LiveClientConfig,
     error::{LiveClientError, LiveClientResult},
     response::ResponseInfo,
+    retry::{Attempt, Attempts, RetryPolicy},
+    transport::{ReqwestTransport, Transport, TransportRequest, TransportResponse},
 };
 
-use crate::live::cert;
-
 pub struct LiveClient {
     config: LiveClientConfig,
-    client: Client,
+    transport: Box<dyn Transport>,
 }
 
 impl LiveClient {
+    /// Builds a client, panicking if the certificate or http client cannot be set up.
+    /// Use [`LiveClient::try_new`] to handle those errors instead.
     pub fn new(config: impl Into<LiveClientConfig>) -> Self {
+        Self::try_new(config).expect("Failed to build LiveClient.")
+    }
+
+    pub fn try_new(config: impl Into<LiveClientConfig>) -> LiveClientResult<Self> {
+        let config = config.into();
+        let transport = ReqwestTransport::new(&config)?;
+        Ok(Self::with_transport(config, transport))
+    }
+
+    /// Builds a client sending its requests through `transport` instead of reqwest.
+    pub fn with_transport(
+        config: impl Into<LiveClientConfig>,
+        transport: impl Transport + 'static,
+    ) -> Self {
         Self {
             config: config.into(),
-            client: Client::builder()
-                .add_root_certificate(cert::get_certificate())
-                .build()
-                .unwrap(), //TODO riot pem
+            transport: Box::new(transport),
         }
     }
 
-    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
-        self.client
-            .request(method, format!("{}{}", self.config.base_url, path))
+    pub fn request(&self, method: Method, path: &str) -> TransportRequest {
+        TransportRequest::new(method, path)
     }
 
     pub async fn execute<'a, T: serde::de::DeserializeOwned + 'a>(
         &self,
-        request: RequestBuilder,
+        request: TransportRequest,
     ) -> LiveClientResult<T> {
         let rinfo = self.execute_raw(request).await?;
-        let status = rinfo.response.status();
-        let value = rinfo.response.json::<T>().await;
-        value.map_err(|e| LiveClientError::new(e, rinfo.retries, None, Some(status)))
+        LiveClientError::decode(&rinfo.response.body, rinfo.retries)
+    }
+
+    /// Sends a request once, without retries or checking the status.
+    pub async fn send(&self, request: &TransportRequest) -> LiveClientResult<TransportResponse> {
+        self.transport.send(request).await
     }
 
-    pub async fn execute_raw(&self, request: RequestBuilder) -> LiveClientResult<ResponseInfo> {
-        let mut retries: u8 = 0;
+    pub async fn execute_raw(&self, request: TransportRequest) -> LiveClientResult<ResponseInfo> {
+        self.execute_raw_with_policy(request, &self.config.retry_policy)
+            .await
+    }
+
+    pub(crate) async fn execute_raw_with_policy(
+        &self,
+        request: TransportRequest,
+        policy: &RetryPolicy,
+    ) -> LiveClientResult<ResponseInfo> {
+        let mut attempts = Attempts::new(policy);
         loop {
-            let request_clone = request
-                .try_clone()
-                .expect("Failed to clone request.")
-                .send();
-            let response = request_clone
-                .await
-                .map_err(|e| LiveClientError::new(e, retries, None, None))?;
-
-            let status = response.status();
-            if status.is_success() {
-                break Ok(ResponseInfo { response, retries });
-            } else if retries > self.config.retries {
-                let err = response.error_for_status_ref().err().unwrap();
-                break Err(LiveClientError::new(err, retries, None, None));
+            match attempts.check(self.transport.send(&request).await) {
+                Attempt::Done(result) => break result,
+                Attempt::RetryAfter(delay) => tokio::time::sleep(delay).await,
             }
-            retries += 1;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::cert::CertificateSource;
+
+    #[test]
+    fn try_new_bad_certificate() {
+        let config = LiveClientConfig::builder()
+            .certificate(CertificateSource::File("./missing.pem".into()))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            LiveClient::try_new(config),
+            Err(LiveClientError::Certificate { .. })
+        ));
+    }
+}
@@ -1,16 +1,334 @@
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use reqwest::{Certificate, ClientBuilder, Url};
+
+use crate::live::cert::CertificateSource;
+use crate::live::error::{LiveClientError, LiveClientResult};
+use crate::live::retry::RetryPolicy;
+
 pub struct LiveClientConfig {
     pub base_url: String,
-    pub retries: u8,
+    pub retry_policy: RetryPolicy,
+    /// Timeout for a whole request, from connecting until the body is read.
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    pub certificate: CertificateSource,
+    pub user_agent: Option<String>,
 }
 
 impl LiveClientConfig {
     pub const DEFAULT_BASE_URL: &'static str = "https://127.0.0.1:2999";
-    pub const DEFAULT_RETRIES: u8 = 3;
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+    pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+    #[deprecated(note = "use `RetryPolicy::DEFAULT_RETRIES`")]
+    pub const DEFAULT_RETRIES: u8 = RetryPolicy::DEFAULT_RETRIES;
+
+    pub const ENV_BASE_URL: &'static str = "RUMBLE_BASE_URL";
+    pub const ENV_PORT: &'static str = "RUMBLE_PORT";
+    pub const ENV_RETRIES: &'static str = "RUMBLE_RETRIES";
+    pub const ENV_TIMEOUT_MS: &'static str = "RUMBLE_TIMEOUT_MS";
+    pub const ENV_CONNECT_TIMEOUT_MS: &'static str = "RUMBLE_CONNECT_TIMEOUT_MS";
+    pub const ENV_CERT_PATH: &'static str = "RUMBLE_CERT_PATH";
+    pub const ENV_CERT_SHA256: &'static str = "RUMBLE_CERT_SHA256";
+    pub const ENV_USER_AGENT: &'static str = "RUMBLE_USER_AGENT";
 
     pub fn new() -> Self {
         Self {
             base_url: Self::DEFAULT_BASE_URL.into(),
-            retries: Self::DEFAULT_RETRIES,
+            retry_policy: RetryPolicy::new(),
+            timeout: Some(Self::DEFAULT_TIMEOUT),
+            connect_timeout: Some(Self::DEFAULT_CONNECT_TIMEOUT),
+            certificate: CertificateSource::Embedded,
+            user_agent: None,
         }
     }
+
+    pub fn builder() -> LiveClientConfigBuilder {
+        LiveClientConfigBuilder::new()
+    }
+
+    /// Default config with the `RUMBLE_*` environment variables applied on top.
+    pub fn from_env() -> LiveClientResult<Self> {
+        Self::builder().with_env()?.build()
+    }
+
+    /// Number of retries, now part of the retry policy.
+    #[deprecated(note = "use `retry_policy.max_retries`")]
+    pub fn retries(&self) -> u8 {
+        self.retry_policy.max_retries
+    }
+
+    #[deprecated(note = "use `retry_policy.max_retries`")]
+    pub fn set_retries(&mut self, retries: u8) {
+        self.retry_policy.max_retries = retries;
+    }
+
+    /// Applies certificate, timeouts and user agent to an http client builder.
+    pub(crate) fn configure<B: HttpClientBuilder>(&self, builder: B) -> LiveClientResult<B> {
+        let mut builder = self.certificate.apply(builder)?;
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        Ok(builder)
+    }
+}
+
+/// The settings shared by the async and blocking reqwest client builders.
+pub(crate) trait HttpClientBuilder: Sized {
+    fn use_rustls_tls(self) -> Self;
+    fn tls_built_in_root_certs(self, enabled: bool) -> Self;
+    fn add_root_certificate(self, certificate: Certificate) -> Self;
+    fn danger_accept_invalid_certs(self, accept: bool) -> Self;
+    fn use_preconfigured_tls(self, tls: rustls::ClientConfig) -> Self;
+    fn timeout(self, timeout: Duration) -> Self;
+    fn connect_timeout(self, timeout: Duration) -> Self;
+    fn user_agent(self, user_agent: &str) -> Self;
+}
+
+macro_rules! impl_http_client_builder {
+    ($builder:ty) => {
+        impl HttpClientBuilder for $builder {
+            fn use_rustls_tls(self) -> Self {
+                self.use_rustls_tls()
+            }
+
+            fn tls_built_in_root_certs(self, enabled: bool) -> Self {
+                self.tls_built_in_root_certs(enabled)
+            }
+
+            fn add_root_certificate(self, certificate: Certificate) -> Self {
+                self.add_root_certificate(certificate)
+            }
+
+            fn danger_accept_invalid_certs(self, accept: bool) -> Self {
+                self.danger_accept_invalid_certs(accept)
+            }
+
+            fn use_preconfigured_tls(self, tls: rustls::ClientConfig) -> Self {
+                self.use_preconfigured_tls(tls)
+            }
+
+            fn timeout(self, timeout: Duration) -> Self {
+                self.timeout(timeout)
+            }
+
+            fn connect_timeout(self, timeout: Duration) -> Self {
+                self.connect_timeout(timeout)
+            }
+
+            fn user_agent(self, user_agent: &str) -> Self {
+                self.user_agent(user_agent)
+            }
+        }
+    };
+}
+
+impl_http_client_builder!(ClientBuilder);
+#[cfg(feature = "blocking")]
+impl_http_client_builder!(reqwest::blocking::ClientBuilder);
+
+impl Default for LiveClientConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct LiveClientConfigBuilder {
+    config: LiveClientConfig,
+    port: Option<u16>,
+}
+
+impl LiveClientConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: LiveClientConfig::new(),
+            port: None,
+        }
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.config.base_url = base_url.into();
+        self
+    }
+
+    /// Replaces the port of the base url.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    pub fn retries(mut self, retries: u8) -> Self {
+        self.config.retry_policy.max_retries = retries;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.config.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn certificate(mut self, certificate: CertificateSource) -> Self {
+        self.config.certificate = certificate;
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Applies the `RUMBLE_*` environment variables that are set.
+    pub fn with_env(self) -> LiveClientResult<Self> {
+        self.with_vars(|key| env::var(key).ok())
+    }
+
+    fn with_vars(mut self, var: impl Fn(&str) -> Option<String>) -> LiveClientResult<Self> {
+        if let Some(base_url) = var(LiveClientConfig::ENV_BASE_URL) {
+            self = self.base_url(base_url);
+        }
+        if let Some(port) = parse_var(&var, LiveClientConfig::ENV_PORT)? {
+            self = self.port(port);
+        }
+        if let Some(retries) = parse_var(&var, LiveClientConfig::ENV_RETRIES)? {
+            self = self.retries(retries);
+        }
+        if let Some(timeout) = parse_var(&var, LiveClientConfig::ENV_TIMEOUT_MS)? {
+            self = self.timeout(Duration::from_millis(timeout));
+        }
+        if let Some(timeout) = parse_var(&var, LiveClientConfig::ENV_CONNECT_TIMEOUT_MS)? {
+            self = self.connect_timeout(Duration::from_millis(timeout));
+        }
+        if let Some(path) = var(LiveClientConfig::ENV_CERT_PATH) {
+            self = self.certificate(CertificateSource::File(PathBuf::from(path)));
+        }
+        if let Some(fingerprint) = var(LiveClientConfig::ENV_CERT_SHA256) {
+            self = self.certificate(CertificateSource::sha256_pin(&fingerprint)?);
+        }
+        if let Some(user_agent) = var(LiveClientConfig::ENV_USER_AGENT) {
+            self = self.user_agent(user_agent);
+        }
+        Ok(self)
+    }
+
+    pub fn build(mut self) -> LiveClientResult<LiveClientConfig> {
+        let mut url = Url::parse(&self.config.base_url).map_err(|e| LiveClientError::Config {
+            message: format!("invalid base url `{}`: {}", self.config.base_url, e),
+        })?;
+        if let Some(port) = self.port {
+            url.set_port(Some(port))
+                .map_err(|_| LiveClientError::Config {
+                    message: format!("cannot set a port on `{}`", url),
+                })?;
+        }
+        // paths are appended to the base url, which must not end with a slash
+        self.config.base_url = url.as_str().trim_end_matches('/').into();
+        Ok(self.config)
+    }
+}
+
+impl Default for LiveClientConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_var<T: FromStr>(
+    var: &impl Fn(&str) -> Option<String>,
+    key: &str,
+) -> LiveClientResult<Option<T>>
+where
+    T::Err: std::fmt::Display,
+{
+    var(key)
+        .map(|value| {
+            value.parse().map_err(|e| LiveClientError::Config {
+                message: format!("invalid {} `{}`: {}", key, value, e),
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn builder() {
+        let config = LiveClientConfig::builder()
+            .base_url("https://localhost:1234/")
+            .port(2998)
+            .retries(5)
+            .timeout(Duration::from_secs(1))
+            .user_agent("rumble-test")
+            .build()
+            .unwrap();
+        assert_eq!(config.base_url, "https://localhost:2998");
+        assert_eq!(config.retry_policy.max_retries, 5);
+        assert_eq!(config.timeout, Some(Duration::from_secs(1)));
+        assert_eq!(config.user_agent.as_deref(), Some("rumble-test"));
+        assert_eq!(config.certificate, CertificateSource::Embedded);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_retries() {
+        let mut config = LiveClientConfig::new();
+        assert_eq!(config.retries(), LiveClientConfig::DEFAULT_RETRIES);
+        config.set_retries(1);
+        assert_eq!(config.retry_policy.max_retries, 1);
+    }
+
+    #[test]
+    fn builder_invalid_url() {
+        let config = LiveClientConfig::builder().base_url("not a url").build();
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn env_vars() {
+        let vars: HashMap<&str, &str> = HashMap::from([
+            (LiveClientConfig::ENV_BASE_URL, "https://10.0.0.2:2999"),
+            (LiveClientConfig::ENV_PORT, "3000"),
+            (LiveClientConfig::ENV_TIMEOUT_MS, "250"),
+            (LiveClientConfig::ENV_CERT_PATH, "./riotgames.pem"),
+        ]);
+        let config = LiveClientConfigBuilder::new()
+            .with_vars(|key| vars.get(key).map(|v| v.to_string()))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.base_url, "https://10.0.0.2:3000");
+        assert_eq!(config.timeout, Some(Duration::from_millis(250)));
+        assert_eq!(
+            config.certificate,
+            CertificateSource::File(PathBuf::from("./riotgames.pem"))
+        );
+    }
+
+    #[test]
+    fn env_vars_invalid() {
+        let result = LiveClientConfigBuilder::new()
+            .with_vars(|key| (key == LiveClientConfig::ENV_RETRIES).then(|| "many".to_string()));
+        assert!(result.is_err());
+    }
 }
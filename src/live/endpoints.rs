@@ -1,79 +1,111 @@
-use reqwest::Method;
+use std::time::Duration;
 
 use crate::live::client::LiveClient;
-use crate::live::error::{LiveClientError, LiveClientResult};
+use crate::live::error::LiveClientResult;
 use crate::live::models::*;
+use crate::live::request;
+use crate::live::sequence::Sequence;
 
 impl LiveClient {
-    pub fn player_api(&self) -> PlayerApi {
-        PlayerApi { client: self }
+    pub fn player_api(&self) -> PlayerApi<'_> {
+        PlayerApi {
+            client: self,
+            timeout: None,
+        }
     }
 
-    pub fn game_api(&self) -> GameApi {
-        GameApi { client: self }
+    pub fn game_api(&self) -> GameApi<'_> {
+        GameApi {
+            client: self,
+            timeout: None,
+        }
+    }
+
+    /// The Replay API, available while a replay or spectated game is open.
+    pub fn replay_api(&self) -> ReplayApi<'_> {
+        ReplayApi {
+            client: self,
+            timeout: None,
+        }
     }
 }
 
 pub struct PlayerApi<'a> {
     client: &'a LiveClient,
+    timeout: Option<Duration>,
 }
 
 impl<'a> PlayerApi<'a> {
-    pub async fn get_player_score(&self, player_name: &str) -> LiveClientResult<PlayerScore> {
-        let request = self.client.request(
-            Method::GET,
-            &format!("/liveclientdata/playerscores?summonerName={}", player_name),
-        );
+    /// Overrides the configured request timeout for calls made through this api.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub async fn get_player_score(
+        &self,
+        player: impl Into<PlayerId>,
+    ) -> LiveClientResult<PlayerScore> {
+        let request = request::player_score(&player.into()).with_optional_timeout(self.timeout);
         let response = self.client.execute::<PlayerScore>(request).await;
         response
     }
 
-    pub async fn get_player_items(&self, player_name: &str) -> LiveClientResult<Vec<PlayerItem>> {
-        let request = self.client.request(
-            Method::GET,
-            &format!("/liveclientdata/playeritems?summonerName={}", player_name),
-        );
+    pub async fn get_player_items(
+        &self,
+        player: impl Into<PlayerId>,
+    ) -> LiveClientResult<Vec<PlayerItem>> {
+        let request = request::player_items(&player.into()).with_optional_timeout(self.timeout);
         let response = self.client.execute::<Vec<PlayerItem>>(request).await;
         response
     }
 
+    pub async fn get_player_summoner_spells(
+        &self,
+        player: impl Into<PlayerId>,
+    ) -> LiveClientResult<SummonerSpells> {
+        let request =
+            request::player_summoner_spells(&player.into()).with_optional_timeout(self.timeout);
+        let response = self.client.execute::<SummonerSpells>(request).await;
+        response
+    }
+
+    pub async fn get_player_main_runes(
+        &self,
+        player: impl Into<PlayerId>,
+    ) -> LiveClientResult<MainRunes> {
+        let request =
+            request::player_main_runes(&player.into()).with_optional_timeout(self.timeout);
+        let response = self.client.execute::<MainRunes>(request).await;
+        response
+    }
+
     pub async fn get_player_list(&self) -> LiveClientResult<Vec<Player>> {
-        let request = self
-            .client
-            .request(Method::GET, &format!("/liveclientdata/playerlist"));
+        let request = request::player_list().with_optional_timeout(self.timeout);
         let response = self.client.execute::<Vec<Player>>(request).await;
         response
     }
 
     pub async fn get_active_player_abilities(&self) -> LiveClientResult<ActivePlayerAbilities> {
-        let request = self.client.request(
-            Method::GET,
-            &format!("/liveclientdata/activeplayerabilities"),
-        );
+        let request = request::active_player_abilities().with_optional_timeout(self.timeout);
         let response = self.client.execute::<ActivePlayerAbilities>(request).await;
         response
     }
 
     pub async fn get_active_player_runes(&self) -> LiveClientResult<ActivePlayerRunes> {
-        let request = self
-            .client
-            .request(Method::GET, &format!("/liveclientdata/activeplayerrunes"));
+        let request = request::active_player_runes().with_optional_timeout(self.timeout);
         let response = self.client.execute::<ActivePlayerRunes>(request).await;
         response
     }
 
     pub async fn get_active_player(&self) -> LiveClientResult<ActivePlayer> {
-        let request = self
-            .client
-            .request(Method::GET, &format!("/liveclientdata/activeplayer"));
+        let request = request::active_player().with_optional_timeout(self.timeout);
         let response = self.client.execute::<ActivePlayer>(request).await;
         response
     }
 
     pub async fn get_active_player_name(&self) -> LiveClientResult<String> {
-        let request = self
-            .client
-            .request(Method::GET, &format!("/liveclientdata/activeplayername"));
+        let request = request::active_player_name().with_optional_timeout(self.timeout);
         let response = self.client.execute::<String>(request).await;
         response
     }
@@ -81,73 +113,491 @@ impl<'a> PlayerApi<'a> {
 
 pub struct GameApi<'a> {
     client: &'a LiveClient,
+    timeout: Option<Duration>,
 }
 
 impl<'a> GameApi<'a> {
+    /// Overrides the configured request timeout for calls made through this api.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub async fn get_all_game_data(&self) -> LiveClientResult<AllGameData> {
+        let request = request::all_game_data().with_optional_timeout(self.timeout);
+        let response = self.client.execute::<AllGameData>(request).await;
+        response
+    }
+
     pub async fn get_game_stats(&self) -> LiveClientResult<GameStats> {
-        let request = self
-            .client
-            .request(Method::GET, &format!("/liveclientdata/gamestats"));
+        let request = request::game_stats().with_optional_timeout(self.timeout);
         let response = self.client.execute::<GameStats>(request).await;
         response
     }
 
     pub async fn get_game_events(&self) -> LiveClientResult<GameEventAll> {
-        let request = self
-            .client
-            .request(Method::GET, &format!("/liveclientdata/eventdata"));
+        let request = request::game_events().with_optional_timeout(self.timeout);
+        let response = self.client.execute::<GameEventAll>(request).await;
+        response
+    }
+
+    /// Fetches only the events that happened after `event_id`.
+    pub async fn get_game_events_since(&self, event_id: i32) -> LiveClientResult<GameEventAll> {
+        let request = request::game_events_since(event_id).with_optional_timeout(self.timeout);
         let response = self.client.execute::<GameEventAll>(request).await;
         response
     }
 }
 
+pub struct ReplayApi<'a> {
+    client: &'a LiveClient,
+    timeout: Option<Duration>,
+}
+
+impl<'a> ReplayApi<'a> {
+    /// Overrides the configured request timeout for calls made through this api.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub async fn get_game(&self) -> LiveClientResult<ReplayGame> {
+        let request = request::replay_game().with_optional_timeout(self.timeout);
+        let response = self.client.execute::<ReplayGame>(request).await;
+        response
+    }
+
+    pub async fn get_playback(&self) -> LiveClientResult<PlaybackState> {
+        let request = request::playback().with_optional_timeout(self.timeout);
+        let response = self.client.execute::<PlaybackState>(request).await;
+        response
+    }
+
+    /// Applies `update` and returns the resulting playback state.
+    pub async fn set_playback(&self, update: &PlaybackUpdate) -> LiveClientResult<PlaybackState> {
+        let request = request::set_playback(update)?.with_optional_timeout(self.timeout);
+        let response = self.client.execute::<PlaybackState>(request).await;
+        response
+    }
+
+    pub async fn pause(&self) -> LiveClientResult<PlaybackState> {
+        self.set_playback(&PlaybackUpdate::new().with_paused(true))
+            .await
+    }
+
+    pub async fn play(&self) -> LiveClientResult<PlaybackState> {
+        self.set_playback(&PlaybackUpdate::new().with_paused(false))
+            .await
+    }
+
+    /// Sets the playback speed, `1.0` is real time.
+    pub async fn set_speed(&self, speed: f64) -> LiveClientResult<PlaybackState> {
+        self.set_playback(&PlaybackUpdate::new().with_speed(speed))
+            .await
+    }
+
+    /// Jumps to `time` seconds into the game.
+    pub async fn seek(&self, time: f64) -> LiveClientResult<PlaybackState> {
+        self.set_playback(&PlaybackUpdate::new().with_time(time))
+            .await
+    }
+
+    pub async fn get_render(&self) -> LiveClientResult<RenderSettings> {
+        let request = request::render().with_optional_timeout(self.timeout);
+        let response = self.client.execute::<RenderSettings>(request).await;
+        response
+    }
+
+    /// Sends the fields of `update` and returns the resulting render settings.
+    pub async fn update_render(&self, update: &RenderUpdate) -> LiveClientResult<RenderSettings> {
+        if update.is_empty() {
+            return self.get_render().await;
+        }
+        let request = request::update_render(update)?.with_optional_timeout(self.timeout);
+        let response = self.client.execute::<RenderSettings>(request).await;
+        response
+    }
+
+    pub async fn get_sequence(&self) -> LiveClientResult<Sequence> {
+        let request = request::sequence().with_optional_timeout(self.timeout);
+        let response = self.client.execute::<Sequence>(request).await;
+        response
+    }
+
+    /// Validates `sequence` and starts playing it, replacing the current sequence.
+    pub async fn set_sequence(&self, sequence: &Sequence) -> LiveClientResult<Sequence> {
+        sequence.validate()?;
+        let request = request::set_sequence(sequence)?.with_optional_timeout(self.timeout);
+        let response = self.client.execute::<Sequence>(request).await;
+        response
+    }
+
+    /// Stops the current sequence by posting an empty one.
+    pub async fn clear_sequence(&self) -> LiveClientResult<Sequence> {
+        self.set_sequence(&Sequence::default()).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::live::cert::CertificateSource;
     use crate::live::config::LiveClientConfig;
+    use crate::live::error::LiveClientError;
+    use crate::live::mock::{MockGame, MockServer};
+    use crate::live::retry::RetryPolicy;
+    use crate::live::sequence::Keyframe;
+    use crate::live::transport::tests::client;
+    use crate::live::transport::MemoryTransport;
+    use reqwest::Method;
+    use std::sync::Arc;
+
+    async fn mock(game: MockGame) -> (MockServer, LiveClient) {
+        let server = MockServer::start(game.with_speed(0.0)).await.unwrap();
+        let lcu = LiveClient::new(server.config());
+        (server, lcu)
+    }
+
+    #[tokio::test]
+    async fn timeout() {
+        // the listener never accepts, so the tls handshake hangs until the timeout
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut config = LiveClientConfig::new();
+        config.base_url = format!("https://{}", listener.local_addr().unwrap());
+        config.retry_policy = RetryPolicy::none();
+        config.timeout = Some(Duration::from_secs(30));
+        let lcu = LiveClient::new(config);
+
+        let err = lcu
+            .game_api()
+            .with_timeout(Duration::from_millis(100))
+            .get_game_stats()
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout());
+    }
+
+    #[tokio::test]
+    async fn pinned_certificate() {
+        let server = MockServer::start(MockGame::new()).await.unwrap();
+        let mut config = server.config();
+        config.certificate = CertificateSource::Sha256Pin(server.certificate_sha256());
+        let lcu = LiveClient::new(config);
+        assert!(lcu.game_api().get_game_stats().await.is_ok());
+
+        let mut config = server.config();
+        config.certificate = CertificateSource::Sha256Pin([0; 32]);
+        let lcu = LiveClient::new(config);
+        let err = lcu.game_api().get_game_stats().await.unwrap_err();
+        assert!(matches!(err, LiveClientError::Certificate { .. }));
+        // rejected during the handshake, before the server can answer with an error status
+        let err = lcu
+            .player_api()
+            .get_player_score("Nobody")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, LiveClientError::Certificate { .. }));
+    }
+
+    #[tokio::test]
+    async fn system_roots() {
+        let server = MockServer::start(MockGame::new()).await.unwrap();
+        let mut config = server.config();
+        config.certificate = CertificateSource::SystemRoots;
+        let lcu = LiveClient::new(config);
+        // the mock's self signed certificate is not a platform root
+        assert!(lcu.game_api().get_game_stats().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn spectator_mode() {
+        let (_server, lcu) = mock(MockGame::new().spectator(true)).await;
+        let err = lcu.player_api().get_active_player().await.unwrap_err();
+        assert!(err.is_spectator_mode());
+
+        let data = lcu.game_api().get_all_game_data().await.unwrap();
+        assert!(data.active_player.is_none());
+    }
 
     #[tokio::test]
     async fn get_active_player() {
-        let lcu = LiveClient::new(LiveClientConfig::new());
+        let (_server, lcu) = mock(MockGame::new()).await;
         let player: ActivePlayer = lcu.player_api().get_active_player().await.unwrap();
-        println!("{}", player.summoner_name);
+        assert_eq!(player.summoner_name, "Riot Tuxedo");
+    }
+
+    #[tokio::test]
+    async fn get_active_player_name() {
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let name = lcu.player_api().get_active_player_name().await.unwrap();
+        assert_eq!(name, "Riot Tuxedo");
     }
 
     #[tokio::test]
     async fn get_active_player_runes() {
-        let lcu = LiveClient::new(LiveClientConfig::new());
-        let player: ActivePlayerRunes = lcu.player_api().get_active_player_runes().await.unwrap();
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let runes: ActivePlayerRunes = lcu.player_api().get_active_player_runes().await.unwrap();
+        assert_eq!(runes.main_runes.keystone.id, 8112);
     }
 
     #[tokio::test]
     async fn get_active_player_abilities() {
-        let lcu = LiveClient::new(LiveClientConfig::new());
-        let player: ActivePlayerAbilities = lcu
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let abilities: ActivePlayerAbilities = lcu
             .player_api()
             .get_active_player_abilities()
             .await
             .unwrap();
+        assert_eq!(abilities.abilities.len(), 5);
     }
 
     #[tokio::test]
     async fn get_player_list() {
-        let lcu = LiveClient::new(LiveClientConfig::new());
+        let (_server, lcu) = mock(MockGame::new()).await;
         let players: Vec<Player> = lcu.player_api().get_player_list().await.unwrap();
-        println!("{}", players.len());
+        assert_eq!(players.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_player_score() {
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let score: PlayerScore = lcu.player_api().get_player_score("Lux Bot").await.unwrap();
+        assert_eq!(score.kills, 0);
+
+        let err = lcu
+            .player_api()
+            .get_player_score("Nobody")
+            .await
+            .unwrap_err();
+        assert!(err.status().is_some());
+    }
+
+    #[tokio::test]
+    async fn player_by_reference() {
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let name = String::from("Lux Bot");
+        let score = lcu.player_api().get_player_score(&name).await.unwrap();
+        assert_eq!(score.kills, 0);
+
+        let active_player = lcu.player_api().get_active_player().await.unwrap();
+        let items = lcu
+            .player_api()
+            .get_player_items(&active_player)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_player_items() {
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let items: Vec<PlayerItem> = lcu
+            .player_api()
+            .get_player_items("Riot Tuxedo")
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_player_items_riot_id() {
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let players = lcu.player_api().get_player_list().await.unwrap();
+        let items = lcu
+            .player_api()
+            .get_player_items(&players[0])
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 2);
+
+        let items = lcu
+            .player_api()
+            .get_player_items(RiotId::new("Riot Tuxedo", "NA1"))
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 2);
+
+        let err = lcu
+            .player_api()
+            .get_player_items("Riot Tuxedo#EUW")
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(reqwest::StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn get_player_main_runes() {
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let runes: MainRunes = lcu
+            .player_api()
+            .get_player_main_runes("Lux Bot")
+            .await
+            .unwrap();
+        assert_eq!(runes.keystone.display_name, "Arcane Comet");
+    }
+
+    #[tokio::test]
+    async fn get_player_summoner_spells() {
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let spells: SummonerSpells = lcu
+            .player_api()
+            .get_player_summoner_spells("Lux Bot")
+            .await
+            .unwrap();
+        assert_eq!(spells.summoner_spell_two.unwrap().display_name, "Barrier");
     }
 
     #[tokio::test]
     async fn get_game_events() {
-        let lcu = LiveClient::new(LiveClientConfig::new());
+        let (server, lcu) = mock(MockGame::new()).await;
+        server.advance(1000.0);
         let allevents: GameEventAll = lcu.game_api().get_game_events().await.unwrap();
-        println!("{}", allevents.events.len());
-        println!("{:?}", allevents.events);
+        assert_eq!(allevents.events.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn get_all_game_data() {
+        let (_server, lcu) = mock(MockGame::new()).await;
+        let data: AllGameData = lcu.game_api().get_all_game_data().await.unwrap();
+        assert_eq!(data.all_players.len(), 2);
+        assert_eq!(data.events.events.len(), 1);
+        assert!(data.active_player.is_some());
+    }
+
+    #[tokio::test]
+    async fn get_game_events_since() {
+        let (server, lcu) = mock(MockGame::new()).await;
+        server.advance(1000.0);
+        let allevents: GameEventAll = lcu.game_api().get_game_events_since(3).await.unwrap();
+        assert_eq!(allevents.events.len(), 2);
+        assert!(allevents.events.iter().all(|e| e.event_id > 3));
     }
 
     #[tokio::test]
     async fn get_game_stats() {
-        let lcu = LiveClient::new(LiveClientConfig::new());
+        let (server, lcu) = mock(MockGame::new()).await;
+        server.advance(90.0);
         let stats: GameStats = lcu.game_api().get_game_stats().await.unwrap();
-        println!("{:?}", stats);
+        assert_eq!(stats.game_time, 90.0);
+        assert_eq!(stats.game_mode, "CLASSIC");
+    }
+
+    const PLAYBACK: &str = r#"{
+        "length": 1843.2,
+        "paused": true,
+        "seeking": false,
+        "speed": 2.0,
+        "time": 610.5
+    }"#;
+
+    /// A client answered by canned replay responses, with the transport to inspect the requests.
+    fn replay_client() -> (Arc<MemoryTransport>, LiveClient) {
+        let transport = Arc::new(
+            MemoryTransport::new()
+                .with_response("/replay/game", r#"{"processID": 4242}"#)
+                .with_response("/replay/playback", PLAYBACK)
+                .with_response(
+                    "/replay/render",
+                    include_str!("../../fixtures/replay/render.json"),
+                )
+                .with_response("/replay/sequence", "{}"),
+        );
+        (transport.clone(), client(transport))
+    }
+
+    #[tokio::test]
+    async fn get_replay_game() {
+        let (_, lcu) = replay_client();
+        let game = lcu.replay_api().get_game().await.unwrap();
+        assert_eq!(game.process_id, 4242);
+    }
+
+    #[tokio::test]
+    async fn get_playback() {
+        let (_, lcu) = replay_client();
+        let playback = lcu.replay_api().get_playback().await.unwrap();
+        assert!(playback.paused);
+        assert_eq!(playback.time, 610.5);
+    }
+
+    #[tokio::test]
+    async fn set_playback() {
+        let (transport, lcu) = replay_client();
+
+        let playback = lcu.replay_api().seek(610.5).await.unwrap();
+        assert_eq!(playback.length, 1843.2);
+        lcu.replay_api().set_speed(2.0).await.unwrap();
+        lcu.replay_api().pause().await.unwrap();
+
+        let bodies: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|r| String::from_utf8(r.body.clone().unwrap()).unwrap())
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![
+                r#"{"time":610.5}"#,
+                r#"{"speed":2.0}"#,
+                r#"{"paused":true}"#
+            ]
+        );
+        assert!(transport
+            .requests()
+            .iter()
+            .all(|r| r.method == Method::POST));
+    }
+
+    #[tokio::test]
+    async fn update_render() {
+        let (transport, lcu) = replay_client();
+
+        let render = lcu.replay_api().get_render().await.unwrap();
+        assert!(render.fog_of_war);
+        let update = RenderUpdate::new()
+            .with_fog_of_war(false)
+            .with_camera_attached(true);
+        lcu.replay_api().update_render(&update).await.unwrap();
+        lcu.replay_api()
+            .update_render(&RenderUpdate::new())
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1].method, Method::POST);
+        let body: serde_json::Value =
+            serde_json::from_slice(requests[1].body.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"fogOfWar": false, "cameraAttached": true})
+        );
+        assert_eq!(requests[2].method, Method::GET);
+    }
+
+    #[tokio::test]
+    async fn set_sequence() {
+        let (transport, lcu) = replay_client();
+
+        let sequence = Sequence::builder()
+            .playback_speed(0.0, 0.25, Easing::Snap)
+            .playback_speed(5.0, 1.0, Easing::Linear)
+            .build()
+            .unwrap();
+        lcu.replay_api().set_sequence(&sequence).await.unwrap();
+        lcu.replay_api().clear_sequence().await.unwrap();
+
+        let invalid = Sequence {
+            field_of_view: vec![Keyframe::new(0.0, -1.0, Easing::Linear)],
+            ..Sequence::default()
+        };
+        let err = lcu.replay_api().set_sequence(&invalid).await.unwrap_err();
+        assert!(matches!(err, LiveClientError::InvalidRequest { .. }));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        let body: Sequence = serde_json::from_slice(requests[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(body, sequence);
+        assert_eq!(requests[1].body.as_deref(), Some(&b"{}"[..]));
     }
 }
@@ -1,40 +1,239 @@
-use reqwest::{Error, Response, StatusCode};
+use reqwest::StatusCode;
 use std::fmt;
 
+use crate::live::cert;
+
 pub type LiveClientResult<T> = std::result::Result<T, LiveClientError>;
 
 #[derive(Debug)]
-pub struct LiveClientError {
-    error: Error,
-    retries: u8,
-    response: Option<Response>,
-    status_code: Option<StatusCode>,
+pub enum LiveClientError {
+    /// The game client could not be reached, usually because no game is running.
+    Connection { source: reqwest::Error, retries: u8 },
+    /// The game client went away, as reported by a transport without a reqwest error,
+    /// e.g. a replayed recording.
+    Disconnected { message: String, retries: u8 },
+    /// The game client did not answer in time.
+    Timeout { source: reqwest::Error, retries: u8 },
+    /// The game client answered with a non-success status.
+    Status {
+        status: StatusCode,
+        body: String,
+        retries: u8,
+    },
+    /// The response did not match the expected model.
+    Decode {
+        source: serde_json::Error,
+        /// Path to the offending field, e.g. `events.Events[3].EventName`.
+        path: String,
+        payload: String,
+        retries: u8,
+    },
+    /// The endpoint only exists for the active player and is unavailable while spectating.
+    SpectatorMode { retries: u8 },
+    /// Any other failure while building or sending the request.
+    Request { source: reqwest::Error, retries: u8 },
+    /// The client configuration is invalid.
+    Config { message: String },
+    /// The certificate used to trust the game client could not be loaded.
+    Certificate { message: String },
+    /// The request cannot be sent, e.g. because its body cannot be serialized.
+    InvalidRequest { message: String },
 }
 
 impl LiveClientError {
-    pub fn new(
-        error: Error,
+    const SPECTATOR_MODE_MESSAGE: &'static str = "Spectator mode";
+
+    pub(crate) fn from_reqwest(source: reqwest::Error, retries: u8) -> Self {
+        if let Some(message) = cert::pin_mismatch(&source) {
+            LiveClientError::Certificate { message }
+        } else if source.is_timeout() {
+            LiveClientError::Timeout { source, retries }
+        } else if source.is_connect() {
+            LiveClientError::Connection { source, retries }
+        } else {
+            LiveClientError::Request { source, retries }
+        }
+    }
+
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn from_status(status: StatusCode, body: String, retries: u8) -> Self {
+        if body.contains(Self::SPECTATOR_MODE_MESSAGE) {
+            LiveClientError::SpectatorMode { retries }
+        } else {
+            LiveClientError::Status {
+                status,
+                body,
+                retries,
+            }
+        }
+    }
+
+    /// Decodes a response body, keeping the payload and field path on failure.
+    pub(crate) fn decode<T: serde::de::DeserializeOwned>(
+        payload: &[u8],
         retries: u8,
-        response: Option<Response>,
-        status_code: Option<StatusCode>,
-    ) -> Self {
-        Self {
-            error,
-            retries,
-            response,
-            status_code,
+    ) -> LiveClientResult<T> {
+        let deserializer = &mut serde_json::Deserializer::from_slice(payload);
+        serde_path_to_error::deserialize(deserializer).map_err(|e| {
+            let payload = String::from_utf8_lossy(payload).into_owned();
+            if payload.contains(Self::SPECTATOR_MODE_MESSAGE) {
+                return LiveClientError::SpectatorMode { retries };
+            }
+            LiveClientError::Decode {
+                path: e.path().to_string(),
+                source: e.into_inner(),
+                payload,
+                retries,
+            }
+        })
+    }
+
+    /// Records how many retries were made before this error was returned.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn with_retries(mut self, retries: u8) -> Self {
+        match &mut self {
+            LiveClientError::Connection { retries: r, .. }
+            | LiveClientError::Disconnected { retries: r, .. }
+            | LiveClientError::Timeout { retries: r, .. }
+            | LiveClientError::Status { retries: r, .. }
+            | LiveClientError::Decode { retries: r, .. }
+            | LiveClientError::SpectatorMode { retries: r }
+            | LiveClientError::Request { retries: r, .. } => *r = retries,
+            LiveClientError::Config { .. }
+            | LiveClientError::Certificate { .. }
+            | LiveClientError::InvalidRequest { .. } => (),
+        }
+        self
+    }
+
+    /// Number of retries made before giving up.
+    pub fn retries(&self) -> u8 {
+        match self {
+            LiveClientError::Connection { retries, .. }
+            | LiveClientError::Disconnected { retries, .. }
+            | LiveClientError::Timeout { retries, .. }
+            | LiveClientError::Status { retries, .. }
+            | LiveClientError::Decode { retries, .. }
+            | LiveClientError::SpectatorMode { retries }
+            | LiveClientError::Request { retries, .. } => *retries,
+            LiveClientError::Config { .. }
+            | LiveClientError::Certificate { .. }
+            | LiveClientError::InvalidRequest { .. } => 0,
+        }
+    }
+
+    /// HTTP status returned by the game client, if it answered with an error status.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            LiveClientError::Status { status, .. } => Some(*status),
+            _ => None,
         }
     }
+
+    pub fn is_connect(&self) -> bool {
+        matches!(
+            self,
+            LiveClientError::Connection { .. } | LiveClientError::Disconnected { .. }
+        )
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, LiveClientError::Timeout { .. })
+    }
+
+    pub fn is_decode(&self) -> bool {
+        matches!(self, LiveClientError::Decode { .. })
+    }
+
+    pub fn is_spectator_mode(&self) -> bool {
+        matches!(self, LiveClientError::SpectatorMode { .. })
+    }
 }
 
 impl fmt::Display for LiveClientError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:#?}", self)
+        match self {
+            LiveClientError::Connection { source, .. } => {
+                write!(f, "could not connect to the game client: {}", source)
+            }
+            LiveClientError::Disconnected { message, .. } => {
+                write!(f, "could not connect to the game client: {}", message)
+            }
+            LiveClientError::Timeout { .. } => write!(f, "request to the game client timed out"),
+            LiveClientError::Status { status, body, .. } => {
+                write!(f, "game client returned {}: {}", status, body)
+            }
+            LiveClientError::Decode { source, path, .. } => {
+                write!(f, "failed to decode response at `{}`: {}", path, source)
+            }
+            LiveClientError::SpectatorMode { .. } => {
+                write!(f, "endpoint is unavailable in spectator mode")
+            }
+            LiveClientError::Request { source, .. } => write!(f, "request failed: {}", source),
+            LiveClientError::Config { message } => write!(f, "invalid configuration: {}", message),
+            LiveClientError::Certificate { message } => write!(f, "certificate error: {}", message),
+            LiveClientError::InvalidRequest { message } => {
+                write!(f, "invalid request: {}", message)
+            }
+        }
     }
 }
 
 impl std::error::Error for LiveClientError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        Some(&self.error)
+        match self {
+            LiveClientError::Connection { source, .. }
+            | LiveClientError::Timeout { source, .. }
+            | LiveClientError::Request { source, .. } => Some(source),
+            LiveClientError::Decode { source, .. } => Some(source),
+            LiveClientError::Disconnected { .. }
+            | LiveClientError::Status { .. }
+            | LiveClientError::SpectatorMode { .. }
+            | LiveClientError::Config { .. }
+            | LiveClientError::Certificate { .. }
+            | LiveClientError::InvalidRequest { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::models::GameStats;
+
+    #[test]
+    fn decode_path() {
+        let payload = br#"{
+            "gameMode": "CLASSIC",
+            "gameTime": "soon",
+            "mapName": "Map11",
+            "mapNumber": 11,
+            "mapTerrain": "Default"
+        }"#;
+        let err = LiveClientError::decode::<GameStats>(payload, 1).unwrap_err();
+        match err {
+            LiveClientError::Decode {
+                path,
+                payload,
+                retries,
+                ..
+            } => {
+                assert_eq!(path, "gameTime");
+                assert!(payload.contains("soon"));
+                assert_eq!(retries, 1);
+            }
+            _ => panic!("Did not match Decode"),
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    fn spectator_mode() {
+        let body = r#"{"errorCode": "RESOURCE_NOT_FOUND", "httpStatus": 404, "message": "Spectator mode doesn't currently support this feature"}"#;
+        let err = LiveClientError::from_status(StatusCode::NOT_FOUND, body.into(), 0);
+        assert!(err.is_spectator_mode());
+
+        let err = LiveClientError::from_status(StatusCode::NOT_FOUND, "".into(), 0);
+        assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
     }
 }
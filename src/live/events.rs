@@ -0,0 +1,352 @@
+#[cfg(feature = "async")]
+use std::collections::VecDeque;
+#[cfg(feature = "async")]
+use std::time::Duration;
+
+#[cfg(feature = "async")]
+use futures::stream::{self, Stream};
+#[cfg(feature = "async")]
+use serde::de;
+
+#[cfg(feature = "blocking")]
+use crate::live::blocking;
+#[cfg(feature = "async")]
+use crate::live::client::LiveClient;
+#[cfg(feature = "async")]
+use crate::live::endpoints::GameApi;
+#[cfg(feature = "async")]
+use crate::live::error::LiveClientError;
+use crate::live::error::LiveClientResult;
+use crate::live::models::{GameEvent, GameEventAll, SkippedEvent};
+
+#[cfg(feature = "async")]
+const GAME_END: &str = "GameEnd";
+
+/// Remembers the highest `event_id` seen so far, so each poll only returns new events.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EventCursor {
+    last_event_id: Option<i32>,
+    skipped: Vec<SkippedEvent>,
+}
+
+impl EventCursor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts the cursor after an already seen event.
+    pub fn starting_after(event_id: i32) -> Self {
+        Self {
+            last_event_id: Some(event_id),
+            skipped: Vec::new(),
+        }
+    }
+
+    pub fn last_event_id(&self) -> Option<i32> {
+        self.last_event_id
+    }
+
+    /// New entries of the last poll that could not be decoded. They are not returned
+    /// again by later polls.
+    pub fn skipped(&self) -> &[SkippedEvent] {
+        &self.skipped
+    }
+
+    /// Drops the events at or below the high-water mark and advances it past the rest.
+    pub fn advance(&mut self, events: Vec<GameEvent>) -> Vec<GameEvent> {
+        let new_events: Vec<GameEvent> = match self.last_event_id {
+            Some(last) => events.into_iter().filter(|e| e.event_id > last).collect(),
+            None => events,
+        };
+        if let Some(max) = new_events.iter().map(|e| e.event_id).max() {
+            self.last_event_id = Some(max);
+        }
+        new_events
+    }
+
+    /// Fetches the events that happened since the last poll.
+    #[cfg(feature = "async")]
+    pub async fn poll(&mut self, api: &GameApi<'_>) -> LiveClientResult<Vec<GameEvent>> {
+        let all = match self.last_event_id {
+            Some(last) => api.get_game_events_since(last).await?,
+            None => api.get_game_events().await?,
+        };
+        Ok(self.update(all))
+    }
+
+    /// [`EventCursor::poll`] for the blocking client.
+    #[cfg(feature = "blocking")]
+    pub fn poll_blocking(
+        &mut self,
+        api: &blocking::GameApi<'_>,
+    ) -> LiveClientResult<Vec<GameEvent>> {
+        let all = match self.last_event_id {
+            Some(last) => api.get_game_events_since(last)?,
+            None => api.get_game_events()?,
+        };
+        Ok(self.update(all))
+    }
+
+    /// Takes the new events and skipped entries of a poll.
+    fn update(&mut self, all: GameEventAll) -> Vec<GameEvent> {
+        let last = self.last_event_id;
+        self.skipped = all
+            .skipped
+            .into_iter()
+            .filter(|s| match (last, s.event_id()) {
+                (Some(last), Some(id)) => id > last,
+                _ => true,
+            })
+            .collect();
+        let events = self.advance(all.events);
+        // move past skipped entries too, so they are reported only once
+        let skipped_max = self.skipped.iter().filter_map(SkippedEvent::event_id).max();
+        self.last_event_id = self.last_event_id.max(skipped_max);
+        events
+    }
+}
+
+#[cfg(feature = "async")]
+struct EventStreamState {
+    cursor: EventCursor,
+    pending: VecDeque<LiveClientResult<GameEvent>>,
+    polled: bool,
+    connected: bool,
+    ended: bool,
+}
+
+#[cfg(feature = "async")]
+impl LiveClient {
+    /// Polls the event data every `interval` and yields each new event exactly once.
+    ///
+    /// Failed polls are yielded as errors without ending the stream, as are entries
+    /// that fail to decode, once each. The stream ends after the `GameEnd` event, or
+    /// once the game client stops accepting connections after having been reached.
+    pub fn event_stream(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = LiveClientResult<GameEvent>> + '_ {
+        let state = EventStreamState {
+            cursor: EventCursor::new(),
+            pending: VecDeque::new(),
+            polled: false,
+            connected: false,
+            ended: false,
+        };
+        stream::unfold(state, move |mut state| async move {
+            loop {
+                if let Some(item) = state.pending.pop_front() {
+                    if matches!(&item, Ok(event) if event.event_type.name() == GAME_END) {
+                        state.pending.clear();
+                        state.ended = true;
+                    }
+                    return Some((item, state));
+                }
+                if state.ended {
+                    return None;
+                }
+                if state.polled {
+                    tokio::time::sleep(interval).await;
+                }
+                state.polled = true;
+                match state.cursor.poll(&self.game_api()).await {
+                    Ok(events) => {
+                        state.connected = true;
+                        let skipped = state.cursor.skipped();
+                        state.ended = skipped.iter().any(|s| s.name() == Some(GAME_END));
+                        state.pending.extend(stream_items(skipped, events));
+                    }
+                    Err(e) if e.is_connect() && state.connected => return None,
+                    Err(e) => return Some((Err(e), state)),
+                }
+            }
+        })
+    }
+}
+
+/// A poll's events and skipped entries as stream items, in event order.
+#[cfg(feature = "async")]
+fn stream_items(
+    skipped: &[SkippedEvent],
+    events: Vec<GameEvent>,
+) -> Vec<LiveClientResult<GameEvent>> {
+    let mut items: Vec<_> = skipped
+        .iter()
+        .map(|s| (s.event_id(), Err(skipped_error(s))))
+        .chain(events.into_iter().map(|e| (Some(e.event_id), Ok(e))))
+        .collect();
+    items.sort_by_key(|(event_id, _)| *event_id);
+    items.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(feature = "async")]
+fn skipped_error(skipped: &SkippedEvent) -> LiveClientError {
+    LiveClientError::Decode {
+        source: de::Error::custom(&skipped.error),
+        path: format!("Events[{}]", skipped.index),
+        payload: skipped.raw.to_string(),
+        retries: 0,
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use super::*;
+    use crate::live::mock::{MockGame, MockServer};
+    use crate::live::models::GameEventType;
+    use crate::live::retry::RetryPolicy;
+    use crate::live::transport::tests::client;
+    use crate::live::transport::MemoryTransport;
+    use futures::StreamExt;
+
+    fn events(ids: &[i32]) -> Vec<GameEvent> {
+        let events: Vec<String> = ids
+            .iter()
+            .map(|id| {
+                format!(
+                    r#"{{"EventID": {}, "EventName": "MinionsSpawning", "EventTime": 0}}"#,
+                    id
+                )
+            })
+            .collect();
+        let all: GameEventAll =
+            serde_json::from_str(&format!(r#"{{"Events": [{}]}}"#, events.join(","))).unwrap();
+        all.events
+    }
+
+    #[test]
+    fn advance() {
+        let mut cursor = EventCursor::new();
+        assert_eq!(cursor.advance(events(&[0, 1, 2])).len(), 3);
+        assert_eq!(cursor.last_event_id(), Some(2));
+
+        let new_events = cursor.advance(events(&[1, 2, 3, 4]));
+        assert_eq!(new_events.len(), 2);
+        assert_eq!(new_events[0].event_id, 3);
+        assert_eq!(cursor.last_event_id(), Some(4));
+    }
+
+    #[test]
+    fn advance_empty() {
+        let mut cursor = EventCursor::starting_after(5);
+        assert!(cursor.advance(events(&[])).is_empty());
+        assert_eq!(cursor.last_event_id(), Some(5));
+    }
+
+    #[tokio::test]
+    async fn event_stream() {
+        let server = MockServer::start(MockGame::new().with_speed(0.0))
+            .await
+            .unwrap();
+        let mut config = server.config();
+        config.retry_policy = RetryPolicy::none();
+        let lcu = LiveClient::new(config);
+        let stream = lcu.event_stream(Duration::from_millis(10));
+        futures::pin_mut!(stream);
+
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.event_id, 0);
+
+        server.advance(200.0);
+        let second = stream.next().await.unwrap().unwrap();
+        let third = stream.next().await.unwrap().unwrap();
+        assert_eq!((second.event_id, third.event_id), (1, 2));
+
+        server.shutdown().await;
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn event_stream_game_end() {
+        let server = MockServer::start(MockGame::new().with_speed(0.0))
+            .await
+            .unwrap();
+        server.advance(2000.0);
+        let mut config = server.config();
+        config.retry_policy = RetryPolicy::none();
+        let lcu = LiveClient::new(config);
+
+        let events: Vec<GameEvent> = lcu
+            .event_stream(Duration::from_millis(10))
+            .map(|e| e.unwrap())
+            .collect()
+            .await;
+        assert_eq!(events.len(), 7);
+        match events[6].event_type {
+            GameEventType::GameEnd { .. } => (),
+            _ => panic!("Did not match GameEnd"),
+        }
+    }
+
+    fn memory_client(events: &str) -> LiveClient {
+        let transport = MemoryTransport::new().with_response(
+            "/liveclientdata/eventdata",
+            format!(r#"{{"Events": [{}]}}"#, events),
+        );
+        client(transport)
+    }
+
+    const MALFORMED_KILL: &str =
+        r#"{"EventID": 1, "EventName": "ChampionKill", "EventTime": 90.0, "KillerName": 3}"#;
+
+    #[tokio::test]
+    async fn event_stream_ends_on_game_end() {
+        let lcu = memory_client(
+            r#"{"EventID": 0, "EventName": "GameStart", "EventTime": 0.0},
+               {"EventID": 1, "EventName": "GameEnd", "EventTime": 1500.0, "Result": "Win"}"#,
+        );
+
+        let events: Vec<GameEvent> = lcu
+            .event_stream(Duration::from_millis(10))
+            .map(|e| e.unwrap())
+            .collect()
+            .await;
+        let names: Vec<&str> = events.iter().map(|e| e.event_type.name()).collect();
+        assert_eq!(names, ["GameStart", "GameEnd"]);
+    }
+
+    #[tokio::test]
+    async fn poll_skipped() {
+        let lcu = memory_client(&format!(
+            r#"{{"EventID": 0, "EventName": "GameStart", "EventTime": 0.0}},
+               {},
+               {{"EventID": 2, "EventName": "MinionsSpawning", "EventTime": 65.0}}"#,
+            MALFORMED_KILL
+        ));
+        let mut cursor = EventCursor::new();
+
+        let events = cursor.poll(&lcu.game_api()).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(cursor.skipped().len(), 1);
+        assert_eq!(cursor.skipped()[0].event_id(), Some(1));
+        assert_eq!(cursor.last_event_id(), Some(2));
+
+        // the transport answers with every event again, none of which is new
+        assert!(cursor.poll(&lcu.game_api()).await.unwrap().is_empty());
+        assert!(cursor.skipped().is_empty());
+    }
+
+    #[tokio::test]
+    async fn event_stream_skipped() {
+        let lcu = memory_client(&format!(
+            r#"{{"EventID": 0, "EventName": "GameStart", "EventTime": 0.0}},
+               {},
+               {{"EventID": 2, "EventName": "GameEnd", "EventTime": 1500.0, "Result": 0}}"#,
+            MALFORMED_KILL
+        ));
+
+        let items: Vec<LiveClientResult<GameEvent>> =
+            lcu.event_stream(Duration::from_millis(10)).collect().await;
+        assert_eq!(items.len(), 3);
+        assert!(items[0].is_ok());
+        match &items[1] {
+            Err(LiveClientError::Decode { path, payload, .. }) => {
+                assert_eq!(path, "Events[1]");
+                assert!(payload.contains("ChampionKill"));
+            }
+            _ => panic!("Did not match Decode"),
+        }
+        // the malformed GameEnd still ends the stream
+        assert!(items[2].as_ref().unwrap_err().is_decode());
+    }
+}
@@ -0,0 +1,498 @@
+//! A local stand-in for the Live Client Data API, serving `/liveclientdata/*` over HTTPS
+//! from fixture files or a scripted scenario, so code can be tested without a game.
+
+use std::convert::Infallible;
+use std::fs;
+use std::io;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use reqwest::Url;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tokio::net::TcpListener;
+use tokio::task::{JoinHandle, JoinSet};
+use tokio_rustls::rustls;
+use tokio_rustls::TlsAcceptor;
+
+use crate::live::cert::CertificateSource;
+use crate::live::config::LiveClientConfig;
+
+const ACTIVE_PLAYER: &str = include_str!("../../fixtures/mock/activeplayer.json");
+const PLAYER_LIST: &str = include_str!("../../fixtures/mock/playerlist.json");
+const SCENARIO: &str = include_str!("../../fixtures/mock/scenario.json");
+
+const SPECTATOR_MODE_MESSAGE: &str = "Spectator mode doesn't currently support this feature";
+
+/// The game a [`MockServer`] serves.
+///
+/// Game time starts once the loading time has passed and advances at `speed` times real
+/// time. Scheduled events show up in the event data once the game time reaches them.
+#[derive(Debug, Clone)]
+pub struct MockGame {
+    active_player: Value,
+    players: Vec<Value>,
+    game_data: Value,
+    events: Vec<Value>,
+    loading_time: Duration,
+    speed: f64,
+    spectator: bool,
+}
+
+impl MockGame {
+    /// A practice game with the fixtures bundled with the crate.
+    pub fn new() -> Self {
+        Self::from_json(ACTIVE_PLAYER, PLAYER_LIST, SCENARIO)
+            .expect("Failed to parse bundled mock fixtures.")
+    }
+
+    /// Loads `activeplayer.json`, `playerlist.json` and `scenario.json` from `dir`.
+    ///
+    /// The scenario holds the `gamestats` fields besides `gameTime`, and an `events`
+    /// list of event objects whose `EventTime` schedules them.
+    pub fn from_dir(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        Self::from_json(
+            &fs::read_to_string(dir.join("activeplayer.json"))?,
+            &fs::read_to_string(dir.join("playerlist.json"))?,
+            &fs::read_to_string(dir.join("scenario.json"))?,
+        )
+    }
+
+    fn from_json(active_player: &str, players: &str, scenario: &str) -> io::Result<Self> {
+        let parse = |json: &str| -> io::Result<Value> {
+            serde_json::from_str(json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        };
+        let mut scenario = parse(scenario)?;
+        let events = match scenario.as_object_mut().and_then(|s| s.remove("events")) {
+            Some(Value::Array(events)) => events,
+            _ => Vec::new(),
+        };
+        let players = match parse(players)? {
+            Value::Array(players) => players,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "player list is not an array",
+                ))
+            }
+        };
+        let game = Self {
+            active_player: parse(active_player)?,
+            players,
+            game_data: scenario,
+            events: Vec::new(),
+            loading_time: Duration::ZERO,
+            speed: 1.0,
+            spectator: false,
+        };
+        Ok(events.into_iter().fold(game, |game, event| {
+            let time = event["EventTime"].as_f64().unwrap_or_default();
+            game.with_event(time, event)
+        }))
+    }
+
+    pub fn with_active_player(mut self, active_player: Value) -> Self {
+        self.active_player = active_player;
+        self
+    }
+
+    pub fn with_players(mut self, players: Vec<Value>) -> Self {
+        self.players = players;
+        self
+    }
+
+    /// Schedules an event, an object with its `EventName` and fields, at `event_time`.
+    pub fn with_event(mut self, event_time: f64, mut event: Value) -> Self {
+        event["EventTime"] = json!(event_time);
+        let index = self
+            .events
+            .partition_point(|e| e["EventTime"].as_f64().unwrap_or_default() <= event_time);
+        self.events.insert(index, event);
+        self
+    }
+
+    /// How long the api answers with 404s before the game starts.
+    pub fn with_loading_time(mut self, loading_time: Duration) -> Self {
+        self.loading_time = loading_time;
+        self
+    }
+
+    /// Game seconds per real second, 0 freezes the clock.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Serves the game as a spectator, without an active player.
+    pub fn spectator(mut self, spectator: bool) -> Self {
+        self.spectator = spectator;
+        self
+    }
+}
+
+impl Default for MockGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct MockState {
+    game: MockGame,
+    started: Instant,
+    time_offset: f64,
+}
+
+impl MockState {
+    fn is_loading(&self) -> bool {
+        self.started.elapsed() < self.game.loading_time
+    }
+
+    fn game_time(&self) -> f64 {
+        let running = self
+            .started
+            .elapsed()
+            .saturating_sub(self.game.loading_time);
+        running.as_secs_f64() * self.game.speed + self.time_offset
+    }
+
+    /// Events that happened so far, numbered in order.
+    fn events(&self) -> Vec<Value> {
+        let game_time = self.game_time();
+        self.game
+            .events
+            .iter()
+            .take_while(|e| e["EventTime"].as_f64().unwrap_or_default() <= game_time)
+            .enumerate()
+            .map(|(id, event)| {
+                let mut event = event.clone();
+                event["EventID"] = json!(id);
+                event
+            })
+            .collect()
+    }
+
+    fn game_stats(&self) -> Value {
+        let mut stats = self.game.game_data.clone();
+        stats["gameTime"] = json!(self.game_time());
+        stats
+    }
+
+    /// The player selected by a `riotId` or `summonerName` query.
+    fn player(&self, query: &[(String, String)]) -> Option<&Value> {
+        let (key, id) = query
+            .iter()
+            .find(|(key, _)| key == "riotId" || key == "summonerName")?;
+        self.game
+            .players
+            .iter()
+            .find(|p| p[key.as_str()].as_str() == Some(id.as_str()))
+    }
+
+    fn route(&self, path: &str, query: &[(String, String)]) -> (StatusCode, Value) {
+        let not_found = || {
+            (
+                StatusCode::NOT_FOUND,
+                json!({
+                    "errorCode": "RESOURCE_NOT_FOUND",
+                    "httpStatus": 404,
+                    "message": format!("No route for {}", path),
+                }),
+            )
+        };
+        let spectator = || {
+            (
+                StatusCode::NOT_FOUND,
+                json!({
+                    "errorCode": "RESOURCE_NOT_FOUND",
+                    "httpStatus": 404,
+                    "message": SPECTATOR_MODE_MESSAGE,
+                }),
+            )
+        };
+        if self.is_loading() {
+            return not_found();
+        }
+
+        let endpoint = match path.strip_prefix("/liveclientdata/") {
+            Some(endpoint) => endpoint,
+            None => return not_found(),
+        };
+        let active_player = &self.game.active_player;
+        let ok = |value: Value| (StatusCode::OK, value);
+        match endpoint {
+            "allgamedata" => ok(json!({
+                "activePlayer": if self.game.spectator {
+                    json!({ "error": SPECTATOR_MODE_MESSAGE })
+                } else {
+                    active_player.clone()
+                },
+                "allPlayers": self.game.players,
+                "events": { "Events": self.events() },
+                "gameData": self.game_stats(),
+            })),
+            "activeplayer" | "activeplayername" | "activeplayerabilities" | "activeplayerrunes"
+                if self.game.spectator =>
+            {
+                spectator()
+            }
+            "activeplayer" => ok(active_player.clone()),
+            "activeplayername" => ok(active_player["summonerName"].clone()),
+            "activeplayerabilities" => ok(active_player["abilities"].clone()),
+            "activeplayerrunes" => ok(active_player["fullRunes"].clone()),
+            "playerlist" => ok(json!(self.game.players)),
+            "playerscores" | "playeritems" | "playersummonerspells" | "playermainrunes" => {
+                let field = match endpoint {
+                    "playerscores" => "scores",
+                    "playeritems" => "items",
+                    "playersummonerspells" => "summonerSpells",
+                    _ => "runes",
+                };
+                match self.player(query) {
+                    Some(player) => ok(player[field].clone()),
+                    None => not_found(),
+                }
+            }
+            "eventdata" => {
+                let since = query
+                    .iter()
+                    .find(|(key, _)| key == "eventID")
+                    .and_then(|(_, value)| value.parse::<u64>().ok())
+                    .unwrap_or(0);
+                let events: Vec<Value> = self
+                    .events()
+                    .into_iter()
+                    .filter(|e| e["EventID"].as_u64().unwrap_or_default() >= since)
+                    .collect();
+                ok(json!({ "Events": events }))
+            }
+            "gamestats" => ok(self.game_stats()),
+            _ => not_found(),
+        }
+    }
+}
+
+struct MockTls {
+    ca_pem: String,
+    leaf_der: Vec<u8>,
+    acceptor: TlsAcceptor,
+}
+
+impl MockTls {
+    /// Issues a fresh CA and a `127.0.0.1`/`localhost` certificate signed by it,
+    /// mirroring how the game's certificate is signed by the Riot Games root.
+    fn generate() -> io::Result<Self> {
+        let to_io = |e: rcgen::RcgenError| io::Error::other(e);
+
+        let mut ca_params = rcgen::CertificateParams::new(Vec::new());
+        ca_params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        ca_params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, "rumble mock root");
+        let ca = rcgen::Certificate::from_params(ca_params).map_err(to_io)?;
+
+        let leaf_params =
+            rcgen::CertificateParams::new(vec!["127.0.0.1".to_string(), "localhost".to_string()]);
+        let leaf = rcgen::Certificate::from_params(leaf_params).map_err(to_io)?;
+        let leaf_der = leaf.serialize_der_with_signer(&ca).map_err(to_io)?;
+
+        let config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![rustls::Certificate(leaf_der.clone())],
+                rustls::PrivateKey(leaf.serialize_private_key_der()),
+            )
+            .map_err(io::Error::other)?;
+
+        Ok(Self {
+            ca_pem: ca.serialize_pem().map_err(to_io)?,
+            leaf_der,
+            acceptor: TlsAcceptor::from(Arc::new(config)),
+        })
+    }
+}
+
+/// A running mock server. It stops when dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    ca_pem: String,
+    leaf_der: Vec<u8>,
+    state: Arc<Mutex<MockState>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts serving `game` on a free local port.
+    pub async fn start(game: MockGame) -> io::Result<Self> {
+        Self::bind(SocketAddr::from(([127, 0, 0, 1], 0)), game).await
+    }
+
+    pub async fn bind(addr: SocketAddr, game: MockGame) -> io::Result<Self> {
+        let tls = MockTls::generate()?;
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState {
+            game,
+            started: Instant::now(),
+            time_offset: 0.0,
+        }));
+
+        let acceptor = tls.acceptor;
+        let task_state = state.clone();
+        let task = tokio::spawn(async move {
+            // dropping the set when the server stops closes open keep-alive connections
+            let mut connections = JoinSet::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                let state = task_state.clone();
+                connections.spawn(async move {
+                    let stream = match acceptor.accept(stream).await {
+                        Ok(stream) => stream,
+                        Err(_) => return,
+                    };
+                    let service = service_fn(move |request| {
+                        let response = handle(&state, request);
+                        async move { Ok::<_, Infallible>(response) }
+                    });
+                    let _ = Http::new().serve_connection(stream, service).await;
+                });
+            }
+        });
+
+        Ok(Self {
+            addr,
+            ca_pem: tls.ca_pem,
+            leaf_der: tls.leaf_der,
+            state,
+            task,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("https://{}", self.addr)
+    }
+
+    /// PEM of the CA that signed the server certificate.
+    pub fn ca_pem(&self) -> &str {
+        &self.ca_pem
+    }
+
+    /// SHA-256 digest of the server certificate, for [`CertificateSource::Sha256Pin`].
+    pub fn certificate_sha256(&self) -> [u8; 32] {
+        Sha256::digest(&self.leaf_der).into()
+    }
+
+    /// A client config pointing at this server and trusting its certificate.
+    pub fn config(&self) -> LiveClientConfig {
+        LiveClientConfig::builder()
+            .base_url(self.base_url())
+            .certificate(CertificateSource::Pem(self.ca_pem.clone().into_bytes()))
+            .build()
+            .expect("Failed to build mock config.")
+    }
+
+    pub fn game_time(&self) -> f64 {
+        self.state.lock().unwrap().game_time()
+    }
+
+    /// Moves the game clock forward by `seconds`.
+    pub fn advance(&self, seconds: f64) {
+        self.state.lock().unwrap().time_offset += seconds;
+    }
+
+    /// Stops accepting requests and closes open connections.
+    pub async fn shutdown(mut self) {
+        self.task.abort();
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn handle(state: &Mutex<MockState>, request: Request<Body>) -> Response<Body> {
+    let uri = format!("https://mock{}", request.uri());
+    let query: Vec<(String, String)> = Url::parse(&uri)
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default();
+    let (status, body) = state.lock().unwrap().route(request.uri().path(), &query);
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("Failed to build mock response.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(game: MockGame) -> MockState {
+        MockState {
+            game: game.with_speed(0.0),
+            started: Instant::now(),
+            time_offset: 0.0,
+        }
+    }
+
+    #[test]
+    fn from_dir() {
+        let game = MockGame::from_dir("./fixtures/mock").unwrap();
+        assert_eq!(game.players.len(), 2);
+        assert_eq!(game.game_data["gameMode"], "CLASSIC");
+        assert!(MockGame::from_dir("./fixtures/missing").is_err());
+    }
+
+    #[test]
+    fn scheduled_events() {
+        let mut state = state(MockGame::new().with_event(
+            100.0,
+            json!({"EventName": "Multikill", "KillerName": "Riot Tuxedo", "KillStreak": 2}),
+        ));
+        assert_eq!(state.events().len(), 1);
+
+        state.time_offset = 160.0;
+        let events = state.events();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[2]["EventName"], "Multikill");
+        assert_eq!(events[3]["EventID"], 3);
+
+        let (_, since) = state.route(
+            "/liveclientdata/eventdata",
+            &[("eventID".to_string(), "2".to_string())],
+        );
+        assert_eq!(since["Events"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn loading_and_spectator() {
+        let state = state(
+            MockGame::new()
+                .with_loading_time(Duration::from_secs(60))
+                .spectator(true),
+        );
+        let (status, _) = state.route("/liveclientdata/gamestats", &[]);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let state = MockState {
+            started: Instant::now() - Duration::from_secs(61),
+            ..state
+        };
+        let (status, body) = state.route("/liveclientdata/activeplayer", &[]);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["message"], SPECTATOR_MODE_MESSAGE);
+    }
+}
@@ -1,7 +1,27 @@
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod cert;
+#[cfg(feature = "async")]
 pub mod client;
 pub mod config;
+#[cfg(feature = "async")]
 pub mod endpoints;
 pub mod error;
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub mod events;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod models;
+#[cfg(all(feature = "async", any(test, feature = "recorder")))]
+pub mod recorder;
+#[cfg(all(feature = "async", any(test, feature = "recorder")))]
+pub mod replay;
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod request;
 pub mod response;
+pub mod retry;
+pub mod sequence;
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub mod state;
+pub mod transport;
+mod util;
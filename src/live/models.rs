@@ -1,6 +1,65 @@
 use std::collections::HashMap;
+use std::fmt;
 
-use serde::{de, Deserialize, Deserializer};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::live::util::percent_encode;
+
+/// Declares an enum for a string field of the API, with an `Other` variant for values
+/// added after this version. Values are (de)serialized as the strings they were read from.
+macro_rules! string_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant,)*
+            Other(String),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Other(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Other(other.to_string()),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                Ok($name::from(String::deserialize(deserializer)?.as_str()))
+            }
+        }
+    };
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -35,7 +94,9 @@ pub struct Player {
     pub is_dead: bool,
     pub items: Vec<PlayerItem>,
     pub level: i32,
-    pub position: String,
+    /// `None` when the game has no assigned positions, e.g. in practice tool or ARAM.
+    #[serde(with = "position_or_empty")]
+    pub position: Option<Position>,
     pub raw_champion_name: String,
     pub respawn_timer: f64,
     pub runes: Option<MainRunes>,
@@ -43,8 +104,26 @@ pub struct Player {
     #[serde(rename = "skinID")]
     pub skin_id: i64,
     pub summoner_name: String,
+    /// `Name#TAG`, missing from clients older than Riot IDs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub riot_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub riot_id_game_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub riot_id_tag_line: Option<String>,
     pub summoner_spells: SummonerSpells,
-    pub team: String,
+    pub team: Team,
+}
+
+impl Player {
+    /// The Riot ID when the client sent one, the summoner name otherwise.
+    pub fn player_id(&self) -> PlayerId {
+        PlayerId::new(
+            &self.summoner_name,
+            self.riot_id_game_name.as_deref(),
+            self.riot_id_tag_line.as_deref(),
+        )
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -56,6 +135,29 @@ pub struct ActivePlayer {
     pub full_runes: ActivePlayerRunes,
     pub level: i32,
     pub summoner_name: String,
+    /// `Name#TAG`, missing from clients older than Riot IDs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub riot_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub riot_id_game_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub riot_id_tag_line: Option<String>,
+}
+
+impl ActivePlayer {
+    /// The Riot ID when the client sent one, the summoner name otherwise.
+    pub fn player_id(&self) -> PlayerId {
+        PlayerId::new(
+            &self.summoner_name,
+            self.riot_id_game_name.as_deref(),
+            self.riot_id_tag_line.as_deref(),
+        )
+    }
+
+    /// [`ActivePlayerAbilities::suggest_level_up`] at the player's current level.
+    pub fn suggest_level_up(&self) -> Option<AbilitySlot> {
+        self.abilities.suggest_level_up(self.level)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -86,7 +188,7 @@ pub struct ChampionStats {
     pub physical_lethality: Option<f64>,
     pub resource_max: f64,
     pub resource_regen_rate: f64,
-    pub resource_type: String,
+    pub resource_type: ResourceType,
     pub resource_value: f64,
     pub spell_vamp: Option<f64>,
     pub tenacity: Option<f64>,
@@ -105,7 +207,90 @@ pub struct Ability {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ActivePlayerAbilities {
     #[serde(flatten)]
-    pub abilities: HashMap<String, Ability>,
+    pub abilities: HashMap<AbilitySlot, Ability>,
+}
+
+impl ActivePlayerAbilities {
+    /// Highest level of each basic ability and of the ultimate.
+    const MAX_BASIC_LEVEL: i32 = 5;
+    const ULTIMATE_LEVELS: [i32; 3] = [6, 11, 16];
+
+    pub fn get(&self, slot: &AbilitySlot) -> Option<&Ability> {
+        self.abilities.get(slot)
+    }
+
+    pub fn passive(&self) -> Option<&Ability> {
+        self.get(&AbilitySlot::Passive)
+    }
+
+    pub fn q(&self) -> Option<&Ability> {
+        self.get(&AbilitySlot::Q)
+    }
+
+    pub fn w(&self) -> Option<&Ability> {
+        self.get(&AbilitySlot::W)
+    }
+
+    pub fn e(&self) -> Option<&Ability> {
+        self.get(&AbilitySlot::E)
+    }
+
+    pub fn r(&self) -> Option<&Ability> {
+        self.get(&AbilitySlot::R)
+    }
+
+    fn level(&self, slot: &AbilitySlot) -> i32 {
+        self.get(slot)
+            .and_then(|ability| ability.ability_level)
+            .unwrap_or(0)
+    }
+
+    /// Skill points spent on Q, W, E and R.
+    pub fn points_spent(&self) -> i32 {
+        AbilitySlot::LEVELED
+            .iter()
+            .map(|slot| self.level(slot))
+            .sum()
+    }
+
+    /// Abilities a skill point can go into at champion `level`, following the usual
+    /// rules: the ultimate at 6, 11 and 16, basic abilities up to rank 5 and no higher
+    /// than half the champion level rounded up. Champions with other rules, like Udyr,
+    /// are not accounted for.
+    pub fn level_ups(&self, level: i32) -> Vec<AbilitySlot> {
+        if self.points_spent() >= level {
+            return Vec::new();
+        }
+        AbilitySlot::LEVELED
+            .iter()
+            .filter(|slot| {
+                let current = self.level(slot);
+                let max = match slot {
+                    AbilitySlot::R => Self::ULTIMATE_LEVELS
+                        .iter()
+                        .filter(|unlock| level >= **unlock)
+                        .count() as i32,
+                    _ => Self::MAX_BASIC_LEVEL.min((level + 1) / 2),
+                };
+                self.get(slot).is_some() && current < max
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Suggests where the next skill point at champion `level` should go: the ultimate
+    /// whenever possible, then the basic ability with the most points, Q first on ties.
+    pub fn suggest_level_up(&self, level: i32) -> Option<AbilitySlot> {
+        let level_ups = self.level_ups(level);
+        if level_ups.contains(&AbilitySlot::R) {
+            return Some(AbilitySlot::R);
+        }
+        // rev so max_by_key keeps the first of equally leveled abilities
+        level_ups
+            .into_iter()
+            .rev()
+            .max_by_key(|slot| self.level(slot))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -178,14 +363,67 @@ pub struct GameEvent {
     pub event_type: GameEventType,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Events are decoded one by one, so a malformed event does not fail the whole list.
+#[derive(Debug, Serialize)]
 pub struct GameEventAll {
     #[serde(rename = "Events")]
     pub events: Vec<GameEvent>,
+    /// Events that could not be decoded, in the order they were received.
+    #[serde(skip)]
+    pub skipped: Vec<SkippedEvent>,
+}
+
+impl<'de> Deserialize<'de> for GameEventAll {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawEvents {
+            #[serde(rename = "Events")]
+            events: Vec<Value>,
+        }
+
+        let raw = RawEvents::deserialize(deserializer)?;
+        let mut all = GameEventAll {
+            events: Vec::with_capacity(raw.events.len()),
+            skipped: Vec::new(),
+        };
+        for (index, raw) in raw.events.into_iter().enumerate() {
+            match GameEvent::deserialize(&raw) {
+                Ok(event) => all.events.push(event),
+                Err(e) => all.skipped.push(SkippedEvent {
+                    index,
+                    error: e.to_string(),
+                    raw,
+                }),
+            }
+        }
+        Ok(all)
+    }
+}
+
+/// An event of a known type whose fields did not match the model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedEvent {
+    /// Position in the `Events` list.
+    pub index: usize,
+    pub error: String,
+    pub raw: Value,
+}
+
+impl SkippedEvent {
+    pub fn event_id(&self) -> Option<i32> {
+        self.raw["EventID"].as_i64().map(|id| id as i32)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.raw["EventName"].as_str()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-#[serde(tag = "EventName")]
+#[serde(tag = "EventName", remote = "Self")]
 pub enum GameEventType {
     GameStart,
     MinionsSpawning,
@@ -197,10 +435,16 @@ pub enum GameEventType {
         #[serde(rename = "Assisters")]
         assisters: Vec<String>,
     },
+    FirstBlood {
+        #[serde(rename = "Recipient")]
+        recipient: String,
+    },
     FirstBrick {
         #[serde(rename = "KillerName")]
         killer_name: String,
     },
+    /// The API reports only destroyed turrets. Plates falling off a turret have no event,
+    /// they are only in the match timeline of the Riot web API.
     TurretKilled {
         #[serde(rename = "KillerName")]
         killer_name: String,
@@ -217,11 +461,20 @@ pub enum GameEventType {
         #[serde(rename = "Assisters")]
         assisters: Vec<String>,
     },
+    /// Sent shortly before a destroyed inhibitor comes back.
+    InhibRespawningSoon {
+        #[serde(rename = "InhibRespawningSoon")]
+        inhib_respawning_soon: String,
+    },
+    InhibRespawned {
+        #[serde(rename = "InhibRespawned")]
+        inhib_respawned: String,
+    },
     DragonKill {
         #[serde(rename = "KillerName")]
         killer_name: String,
         #[serde(rename = "DragonType")]
-        dragon_type: String,
+        dragon_type: DragonType,
         #[serde(deserialize_with = "bool_from_string")]
         #[serde(rename = "Stolen")]
         stolen: bool,
@@ -237,6 +490,16 @@ pub enum GameEventType {
         #[serde(rename = "Assisters")]
         assisters: Vec<String>,
     },
+    /// A voidgrub kill.
+    HordeKill {
+        #[serde(rename = "KillerName")]
+        killer_name: String,
+        #[serde(deserialize_with = "bool_from_string")]
+        #[serde(rename = "Stolen")]
+        stolen: bool,
+        #[serde(rename = "Assisters")]
+        assisters: Vec<String>,
+    },
     BaronKill {
         #[serde(rename = "KillerName")]
         killer_name: String,
@@ -256,8 +519,924 @@ pub enum GameEventType {
         #[serde(rename = "Acer")]
         acer: String,
         #[serde(rename = "AcingTeam")]
-        acing_team: String,
+        acing_team: Team,
+    },
+    /// The last event of a game, the result is from the active player's point of view.
+    GameEnd {
+        #[serde(rename = "Result")]
+        result: GameResult,
     },
+    /// An event added to the API after this version, kept as received.
+    #[serde(skip)]
+    Unknown {
+        name: String,
+        raw: Value,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameResult {
+    Win,
+    Lose,
+}
+
+impl GameEventType {
+    /// Names of the modeled events, anything else decodes to [`GameEventType::Unknown`].
+    const NAMES: &'static [&'static str] = &[
+        "GameStart",
+        "MinionsSpawning",
+        "ChampionKill",
+        "FirstBlood",
+        "FirstBrick",
+        "TurretKilled",
+        "InhibKilled",
+        "InhibRespawningSoon",
+        "InhibRespawned",
+        "DragonKill",
+        "HeraldKill",
+        "HordeKill",
+        "BaronKill",
+        "Multikill",
+        "Ace",
+        "GameEnd",
+    ];
+
+    /// The `EventName` of the event.
+    pub fn name(&self) -> &str {
+        match self {
+            GameEventType::GameStart => "GameStart",
+            GameEventType::MinionsSpawning => "MinionsSpawning",
+            GameEventType::ChampionKill { .. } => "ChampionKill",
+            GameEventType::FirstBlood { .. } => "FirstBlood",
+            GameEventType::FirstBrick { .. } => "FirstBrick",
+            GameEventType::TurretKilled { .. } => "TurretKilled",
+            GameEventType::InhibKilled { .. } => "InhibKilled",
+            GameEventType::InhibRespawningSoon { .. } => "InhibRespawningSoon",
+            GameEventType::InhibRespawned { .. } => "InhibRespawned",
+            GameEventType::DragonKill { .. } => "DragonKill",
+            GameEventType::HeraldKill { .. } => "HeraldKill",
+            GameEventType::HordeKill { .. } => "HordeKill",
+            GameEventType::BaronKill { .. } => "BaronKill",
+            GameEventType::Multikill { .. } => "Multikill",
+            GameEventType::Ace { .. } => "Ace",
+            GameEventType::GameEnd { .. } => "GameEnd",
+            GameEventType::Unknown { name, .. } => name,
+        }
+    }
+
+    /// The turret destroyed by a `TurretKilled` event.
+    pub fn turret(&self) -> Option<TurretId> {
+        match self {
+            GameEventType::TurretKilled { turret_killed, .. } => TurretId::parse(turret_killed),
+            _ => None,
+        }
+    }
+
+    /// The inhibitor an `InhibKilled`, `InhibRespawningSoon` or `InhibRespawned` event is about.
+    pub fn inhibitor(&self) -> Option<InhibitorId> {
+        match self {
+            GameEventType::InhibKilled {
+                inhib_killed: id, ..
+            }
+            | GameEventType::InhibRespawningSoon {
+                inhib_respawning_soon: id,
+            }
+            | GameEventType::InhibRespawned {
+                inhib_respawned: id,
+            } => InhibitorId::parse(id),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GameEventType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = Value::deserialize(deserializer)?;
+        let name = raw["EventName"]
+            .as_str()
+            .ok_or_else(|| de::Error::missing_field("EventName"))?;
+        if GameEventType::NAMES.contains(&name) {
+            GameEventType::deserialize(raw).map_err(de::Error::custom)
+        } else {
+            Ok(GameEventType::Unknown {
+                name: name.to_string(),
+                raw,
+            })
+        }
+    }
+}
+
+impl Serialize for GameEventType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            GameEventType::Unknown { raw, .. } => raw.serialize(serializer),
+            known => GameEventType::serialize(known, serializer),
+        }
+    }
+}
+
+/// The game the Replay API is attached to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayGame {
+    #[serde(rename = "processID")]
+    pub process_id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaybackState {
+    /// Length of the replay in seconds.
+    pub length: f64,
+    pub paused: bool,
+    pub seeking: bool,
+    /// `1.0` is real time.
+    pub speed: f64,
+    /// Game time in seconds.
+    pub time: f64,
+}
+
+/// Changes to the playback, only fields that are set are sent.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaybackUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<f64>,
+}
+
+impl PlaybackUpdate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_paused(mut self, paused: bool) -> Self {
+        self.paused = Some(paused);
+        self
+    }
+
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    pub fn with_time(mut self, time: f64) -> Self {
+        self.time = Some(time);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vector3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// What the replay renders, from `/replay/render`.
+///
+/// Flags without a typed field, like the fog and depth of field settings, are kept in `extra`.
+/// Fields missing from the response keep their default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RenderSettings {
+    pub camera_mode: CameraMode,
+    pub camera_position: Vector3,
+    /// Yaw, pitch and roll in degrees.
+    pub camera_rotation: Vector3,
+    pub camera_attached: bool,
+    pub camera_move_speed: f64,
+    pub camera_look_speed: f64,
+    pub field_of_view: f64,
+    pub near_clip: f64,
+    pub far_clip: f64,
+    /// Name of the selected unit, the camera follows it when attached.
+    pub selection_name: String,
+    pub selection_offset: Vector3,
+    pub fog_of_war: bool,
+    pub outline_hover: bool,
+    pub outline_select: bool,
+    pub floating_text: bool,
+    pub characters: bool,
+    pub environment: bool,
+    pub particles: bool,
+    pub banners: bool,
+    pub health_bar_champions: bool,
+    pub health_bar_minions: bool,
+    pub health_bar_pets: bool,
+    pub health_bar_structures: bool,
+    pub health_bar_wards: bool,
+    pub interface_all: bool,
+    pub interface_announce: bool,
+    pub interface_chat: bool,
+    pub interface_frames: bool,
+    pub interface_kill_callouts: bool,
+    pub interface_minimap: bool,
+    pub interface_neutral_timers: bool,
+    pub interface_quests: bool,
+    pub interface_replay: bool,
+    pub interface_score: bool,
+    pub interface_scoreboard: bool,
+    pub interface_target: bool,
+    pub interface_timeline: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+/// Changes to the render settings, only fields that are set are sent.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_mode: Option<CameraMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_position: Option<Vector3>,
+    /// Yaw, pitch and roll in degrees.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_rotation: Option<Vector3>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_attached: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_move_speed: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_look_speed: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_of_view: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub near_clip: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub far_clip: Option<f64>,
+    /// Name of the selected unit, the camera follows it when attached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_offset: Option<Vector3>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fog_of_war: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_hover: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_select: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floating_text: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub characters: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub particles: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banners: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_champions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_minions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_pets: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_structures: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_wards: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_all: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_announce: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_chat: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_frames: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_kill_callouts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_minimap: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_neutral_timers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_quests: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_replay: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_score: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_scoreboard: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_target: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_timeline: Option<bool>,
+    /// Flags without a typed field, by API name.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+impl RenderUpdate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The fields of `changed` that differ from `current`.
+    pub fn diff(current: &RenderSettings, changed: &RenderSettings) -> Self {
+        let (current, changed) =
+            match (serde_json::to_value(current), serde_json::to_value(changed)) {
+                (Ok(Value::Object(current)), Ok(Value::Object(changed))) => (current, changed),
+                _ => return Self::new(),
+            };
+        let fields = changed
+            .into_iter()
+            .filter(|(key, value)| current.get(key) != Some(value))
+            .collect();
+        serde_json::from_value(Value::Object(fields)).unwrap_or_default()
+    }
+
+    /// Sets a flag without a typed field by its API name, e.g. `depthFogEnabled`.
+    pub fn with_field(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_camera_mode(mut self, mode: CameraMode) -> Self {
+        self.camera_mode = Some(mode);
+        self
+    }
+
+    pub fn with_camera_position(mut self, position: Vector3) -> Self {
+        self.camera_position = Some(position);
+        self
+    }
+
+    pub fn with_camera_rotation(mut self, rotation: Vector3) -> Self {
+        self.camera_rotation = Some(rotation);
+        self
+    }
+
+    pub fn with_camera_attached(mut self, attached: bool) -> Self {
+        self.camera_attached = Some(attached);
+        self
+    }
+
+    pub fn with_camera_move_speed(mut self, speed: f64) -> Self {
+        self.camera_move_speed = Some(speed);
+        self
+    }
+
+    pub fn with_camera_look_speed(mut self, speed: f64) -> Self {
+        self.camera_look_speed = Some(speed);
+        self
+    }
+
+    pub fn with_field_of_view(mut self, field_of_view: f64) -> Self {
+        self.field_of_view = Some(field_of_view);
+        self
+    }
+
+    pub fn with_near_clip(mut self, near_clip: f64) -> Self {
+        self.near_clip = Some(near_clip);
+        self
+    }
+
+    pub fn with_far_clip(mut self, far_clip: f64) -> Self {
+        self.far_clip = Some(far_clip);
+        self
+    }
+
+    pub fn with_selection_name(mut self, name: impl Into<String>) -> Self {
+        self.selection_name = Some(name.into());
+        self
+    }
+
+    pub fn with_selection_offset(mut self, offset: Vector3) -> Self {
+        self.selection_offset = Some(offset);
+        self
+    }
+
+    pub fn with_fog_of_war(mut self, fog_of_war: bool) -> Self {
+        self.fog_of_war = Some(fog_of_war);
+        self
+    }
+
+    pub fn with_outline_hover(mut self, outline_hover: bool) -> Self {
+        self.outline_hover = Some(outline_hover);
+        self
+    }
+
+    pub fn with_outline_select(mut self, outline_select: bool) -> Self {
+        self.outline_select = Some(outline_select);
+        self
+    }
+
+    pub fn with_floating_text(mut self, floating_text: bool) -> Self {
+        self.floating_text = Some(floating_text);
+        self
+    }
+
+    pub fn with_characters(mut self, characters: bool) -> Self {
+        self.characters = Some(characters);
+        self
+    }
+
+    pub fn with_environment(mut self, environment: bool) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    pub fn with_particles(mut self, particles: bool) -> Self {
+        self.particles = Some(particles);
+        self
+    }
+
+    pub fn with_banners(mut self, banners: bool) -> Self {
+        self.banners = Some(banners);
+        self
+    }
+
+    pub fn with_health_bar_champions(mut self, health_bar_champions: bool) -> Self {
+        self.health_bar_champions = Some(health_bar_champions);
+        self
+    }
+
+    pub fn with_health_bar_minions(mut self, health_bar_minions: bool) -> Self {
+        self.health_bar_minions = Some(health_bar_minions);
+        self
+    }
+
+    pub fn with_health_bar_pets(mut self, health_bar_pets: bool) -> Self {
+        self.health_bar_pets = Some(health_bar_pets);
+        self
+    }
+
+    pub fn with_health_bar_structures(mut self, health_bar_structures: bool) -> Self {
+        self.health_bar_structures = Some(health_bar_structures);
+        self
+    }
+
+    pub fn with_health_bar_wards(mut self, health_bar_wards: bool) -> Self {
+        self.health_bar_wards = Some(health_bar_wards);
+        self
+    }
+
+    pub fn with_interface_all(mut self, interface_all: bool) -> Self {
+        self.interface_all = Some(interface_all);
+        self
+    }
+
+    pub fn with_interface_announce(mut self, interface_announce: bool) -> Self {
+        self.interface_announce = Some(interface_announce);
+        self
+    }
+
+    pub fn with_interface_chat(mut self, interface_chat: bool) -> Self {
+        self.interface_chat = Some(interface_chat);
+        self
+    }
+
+    pub fn with_interface_frames(mut self, interface_frames: bool) -> Self {
+        self.interface_frames = Some(interface_frames);
+        self
+    }
+
+    pub fn with_interface_kill_callouts(mut self, interface_kill_callouts: bool) -> Self {
+        self.interface_kill_callouts = Some(interface_kill_callouts);
+        self
+    }
+
+    pub fn with_interface_minimap(mut self, interface_minimap: bool) -> Self {
+        self.interface_minimap = Some(interface_minimap);
+        self
+    }
+
+    pub fn with_interface_neutral_timers(mut self, interface_neutral_timers: bool) -> Self {
+        self.interface_neutral_timers = Some(interface_neutral_timers);
+        self
+    }
+
+    pub fn with_interface_quests(mut self, interface_quests: bool) -> Self {
+        self.interface_quests = Some(interface_quests);
+        self
+    }
+
+    pub fn with_interface_replay(mut self, interface_replay: bool) -> Self {
+        self.interface_replay = Some(interface_replay);
+        self
+    }
+
+    pub fn with_interface_score(mut self, interface_score: bool) -> Self {
+        self.interface_score = Some(interface_score);
+        self
+    }
+
+    pub fn with_interface_scoreboard(mut self, interface_scoreboard: bool) -> Self {
+        self.interface_scoreboard = Some(interface_scoreboard);
+        self
+    }
+
+    pub fn with_interface_target(mut self, interface_target: bool) -> Self {
+        self.interface_target = Some(interface_target);
+        self
+    }
+
+    pub fn with_interface_timeline(mut self, interface_timeline: bool) -> Self {
+        self.interface_timeline = Some(interface_timeline);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllGameData {
+    /// `None` when spectating, the API has no active player in that case.
+    #[serde(deserialize_with = "active_player_or_spectator")]
+    pub active_player: Option<ActivePlayer>,
+    pub all_players: Vec<Player>,
+    pub events: GameEventAll,
+    pub game_data: GameStats,
+}
+
+string_enum! {
+    Team {
+        Order => "ORDER",
+        Chaos => "CHAOS",
+        Neutral => "NEUTRAL",
+    }
+}
+
+string_enum! {
+    AbilitySlot {
+        Passive => "Passive",
+        Q => "Q",
+        W => "W",
+        E => "E",
+        R => "R",
+    }
+}
+
+impl AbilitySlot {
+    /// Slots that skill points go into.
+    const LEVELED: [AbilitySlot; 4] = [
+        AbilitySlot::Q,
+        AbilitySlot::W,
+        AbilitySlot::E,
+        AbilitySlot::R,
+    ];
+}
+
+string_enum! {
+    CameraMode {
+        Top => "top",
+        Fps => "fps",
+        Tps => "tps",
+        Focus => "focus",
+        Path => "path",
+    }
+}
+
+/// Replays open with the top-down camera.
+impl Default for CameraMode {
+    fn default() -> Self {
+        CameraMode::Top
+    }
+}
+
+string_enum! {
+    /// How a sequence blends from a keyframe into the next one.
+    Easing {
+        Linear => "linear",
+        Snap => "snap",
+        SmoothStep => "smoothStep",
+        SmootherStep => "smootherStep",
+        QuadraticEaseIn => "quadraticEaseIn",
+        QuadraticEaseOut => "quadraticEaseOut",
+        QuadraticEaseInOut => "quadraticEaseInOut",
+        CubicEaseIn => "cubicEaseIn",
+        CubicEaseOut => "cubicEaseOut",
+        CubicEaseInOut => "cubicEaseInOut",
+        QuarticEaseIn => "quarticEaseIn",
+        QuarticEaseOut => "quarticEaseOut",
+        QuarticEaseInOut => "quarticEaseInOut",
+        QuinticEaseIn => "quinticEaseIn",
+        QuinticEaseOut => "quinticEaseOut",
+        QuinticEaseInOut => "quinticEaseInOut",
+        SineEaseIn => "sineEaseIn",
+        SineEaseOut => "sineEaseOut",
+        SineEaseInOut => "sineEaseInOut",
+        CircularEaseIn => "circularEaseIn",
+        CircularEaseOut => "circularEaseOut",
+        CircularEaseInOut => "circularEaseInOut",
+        ExponentialEaseIn => "exponentialEaseIn",
+        ExponentialEaseOut => "exponentialEaseOut",
+        ExponentialEaseInOut => "exponentialEaseInOut",
+        ElasticEaseIn => "elasticEaseIn",
+        ElasticEaseOut => "elasticEaseOut",
+        ElasticEaseInOut => "elasticEaseInOut",
+        BackEaseIn => "backEaseIn",
+        BackEaseOut => "backEaseOut",
+        BackEaseInOut => "backEaseInOut",
+        BounceEaseIn => "bounceEaseIn",
+        BounceEaseOut => "bounceEaseOut",
+        BounceEaseInOut => "bounceEaseInOut",
+    }
+}
+
+string_enum! {
+    Position {
+        Top => "TOP",
+        Jungle => "JUNGLE",
+        Middle => "MIDDLE",
+        Bottom => "BOTTOM",
+        Utility => "UTILITY",
+    }
+}
+
+string_enum! {
+    DragonType {
+        Fire => "Fire",
+        Earth => "Earth",
+        Water => "Water",
+        Air => "Air",
+        Hextech => "Hextech",
+        Chemtech => "Chemtech",
+        Elder => "Elder",
+    }
+}
+
+string_enum! {
+    /// What the champion's resource bar holds.
+    ResourceType {
+        Mana => "MANA",
+        Energy => "ENERGY",
+        None => "NONE",
+        Shield => "SHIELD",
+        Battlefury => "BATTLEFURY",
+        Dragonfury => "DRAGONFURY",
+        Rage => "RAGE",
+        Heat => "HEAT",
+        Gnarfury => "GNARFURY",
+        Ferocity => "FEROCITY",
+        Bloodwell => "BLOODWELL",
+        Wind => "WIND",
+        Ammo => "AMMO",
+    }
+}
+
+/// A Riot ID, written `game_name#tag_line`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RiotId {
+    pub game_name: String,
+    pub tag_line: String,
+}
+
+impl RiotId {
+    pub fn new(game_name: impl Into<String>, tag_line: impl Into<String>) -> Self {
+        Self {
+            game_name: game_name.into(),
+            tag_line: tag_line.into(),
+        }
+    }
+
+    /// Parses `Name#TAG`, game names may contain `#` so the last one separates the tag.
+    pub fn parse(id: &str) -> Option<Self> {
+        let (game_name, tag_line) = id.rsplit_once('#')?;
+        if game_name.is_empty() || tag_line.is_empty() {
+            return None;
+        }
+        Some(Self::new(game_name, tag_line))
+    }
+}
+
+impl fmt::Display for RiotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.game_name, self.tag_line)
+    }
+}
+
+/// Selects the player of the per-player endpoints.
+///
+/// Strings convert to a Riot ID when they contain a tag, summoner names cannot contain `#`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PlayerId {
+    SummonerName(String),
+    RiotId(RiotId),
+}
+
+impl PlayerId {
+    fn new(summoner_name: &str, game_name: Option<&str>, tag_line: Option<&str>) -> Self {
+        match (game_name, tag_line) {
+            (Some(game_name), Some(tag_line)) if !game_name.is_empty() && !tag_line.is_empty() => {
+                PlayerId::RiotId(RiotId::new(game_name, tag_line))
+            }
+            _ => PlayerId::SummonerName(summoner_name.to_string()),
+        }
+    }
+
+    /// The percent-encoded query selecting the player, e.g. `riotId=Riot%20Tuxedo%23NA1`.
+    pub fn to_query(&self) -> String {
+        match self {
+            PlayerId::SummonerName(name) => format!("summonerName={}", percent_encode(name)),
+            PlayerId::RiotId(id) => format!("riotId={}", percent_encode(&id.to_string())),
+        }
+    }
+
+    /// `endpoint` with the query selecting the player.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn path(&self, endpoint: &str) -> String {
+        format!("{}?{}", endpoint, self.to_query())
+    }
+}
+
+impl From<&str> for PlayerId {
+    fn from(id: &str) -> Self {
+        match RiotId::parse(id) {
+            Some(riot_id) => PlayerId::RiotId(riot_id),
+            None => PlayerId::SummonerName(id.to_string()),
+        }
+    }
+}
+
+impl From<String> for PlayerId {
+    fn from(id: String) -> Self {
+        PlayerId::from(id.as_str())
+    }
+}
+
+impl From<&String> for PlayerId {
+    fn from(id: &String) -> Self {
+        PlayerId::from(id.as_str())
+    }
+}
+
+impl From<RiotId> for PlayerId {
+    fn from(id: RiotId) -> Self {
+        PlayerId::RiotId(id)
+    }
+}
+
+impl From<&RiotId> for PlayerId {
+    fn from(id: &RiotId) -> Self {
+        PlayerId::RiotId(id.clone())
+    }
+}
+
+impl From<&Player> for PlayerId {
+    fn from(player: &Player) -> Self {
+        player.player_id()
+    }
+}
+
+impl From<&ActivePlayer> for PlayerId {
+    fn from(player: &ActivePlayer) -> Self {
+        player.player_id()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lane {
+    Top,
+    Mid,
+    Bot,
+}
+
+impl Lane {
+    fn parse(lane: &str) -> Option<Self> {
+        match lane {
+            "L" => Some(Lane::Top),
+            "C" => Some(Lane::Mid),
+            "R" => Some(Lane::Bot),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TurretTier {
+    Outer,
+    Inner,
+    /// The turret guarding the inhibitor.
+    Base,
+    Nexus,
+}
+
+/// Team of a structure, `T1` is the blue side.
+fn structure_team(team: &str) -> Option<Team> {
+    match team {
+        "T1" => Some(Team::Order),
+        "T2" => Some(Team::Chaos),
+        _ => None,
+    }
+}
+
+/// A Summoner's Rift turret, parsed from names like `Turret_T2_L_03_A`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TurretId {
+    /// The team owning the turret.
+    pub team: Team,
+    /// Nexus turrets are named after the middle lane.
+    pub lane: Lane,
+    pub tier: TurretTier,
+    /// Number in the name, tells the two nexus turrets apart.
+    pub number: u8,
+}
+
+impl TurretId {
+    pub fn parse(id: &str) -> Option<Self> {
+        let mut parts = id.split('_');
+        if parts.next()? != "Turret" {
+            return None;
+        }
+        let team = structure_team(parts.next()?)?;
+        let lane = Lane::parse(parts.next()?)?;
+        let number: u8 = parts.next()?.parse().ok()?;
+        let tier = match (lane, number) {
+            (Lane::Mid, 5) | (Lane::Top | Lane::Bot, 3) => TurretTier::Outer,
+            (Lane::Mid, 4) | (Lane::Top | Lane::Bot, 2) => TurretTier::Inner,
+            (Lane::Mid, 3) | (Lane::Top | Lane::Bot, 1) => TurretTier::Base,
+            (Lane::Mid, 1 | 2) => TurretTier::Nexus,
+            _ => return None,
+        };
+        Some(TurretId {
+            team,
+            lane,
+            tier,
+            number,
+        })
+    }
+}
+
+/// An inhibitor, parsed from names like `Barracks_T1_C1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InhibitorId {
+    /// The team owning the inhibitor.
+    pub team: Team,
+    pub lane: Lane,
+}
+
+impl InhibitorId {
+    pub fn parse(id: &str) -> Option<Self> {
+        let mut parts = id.split('_');
+        if parts.next()? != "Barracks" {
+            return None;
+        }
+        let team = structure_team(parts.next()?)?;
+        let lane = parts.next()?.strip_suffix('1')?;
+        Some(InhibitorId {
+            team,
+            lane: Lane::parse(lane)?,
+        })
+    }
+}
+
+/// The API sends an empty string for players without a position.
+mod position_or_empty {
+    use super::Position;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(position: &Option<Position>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(position.as_ref().map_or("", |p| p.as_str()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Position>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let position = String::deserialize(deserializer)?;
+        Ok(match position.as_str() {
+            "" => None,
+            other => Some(Position::from(other)),
+        })
+    }
+}
+
+fn active_player_or_spectator<'de, D>(deserializer: D) -> Result<Option<ActivePlayer>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // in spectator mode the active player is replaced by {"error": "..."}
+    let value = serde_json::Value::deserialize(deserializer)?;
+    match value {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::Object(ref map) if map.contains_key("error") => Ok(None),
+        other => ActivePlayer::deserialize(other)
+            .map(Some)
+            .map_err(de::Error::custom),
+    }
 }
 
 fn bool_from_string<'de, D>(deserializer: D) -> Result<bool, D::Error>
@@ -277,8 +1456,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn item() {
         let item_str = r#"{
             "canUse": false,
@@ -399,6 +1580,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::single_match)]
     fn event_kill() {
         let event_str = r#"{
             "Assisters": [
@@ -440,6 +1622,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn event_dragon() {
         let event_str = r#"{
             "EventID": 0,
@@ -456,9 +1639,11 @@ mod tests {
         match e.event_type {
             GameEventType::DragonKill {
                 killer_name,
+                dragon_type,
                 stolen,
                 ..
             } => {
+                assert_eq!(dragon_type, DragonType::Elder);
                 assert_eq!(stolen, false);
                 assert_eq!(killer_name, "Riot Tuxedo");
             }
@@ -466,6 +1651,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn riot_id() {
+        let id = RiotId::parse("Tëst & Co#EUW").unwrap();
+        assert_eq!(id.game_name, "Tëst & Co");
+        assert_eq!(id.to_string(), "Tëst & Co#EUW");
+        assert_eq!(RiotId::parse("Lux Bot"), None);
+        assert_eq!(RiotId::parse("Lux Bot#"), None);
+
+        assert_eq!(
+            PlayerId::from("Tëst & Co#EUW").to_query(),
+            "riotId=T%C3%ABst%20%26%20Co%23EUW"
+        );
+        assert_eq!(
+            PlayerId::from("Lux Bot").to_query(),
+            "summonerName=Lux%20Bot"
+        );
+        assert_eq!(PlayerId::from(&id), PlayerId::RiotId(id.clone()));
+        assert_eq!(
+            PlayerId::from(&"Lux Bot".to_string()),
+            PlayerId::SummonerName("Lux Bot".to_string())
+        );
+    }
+
+    #[test]
+    fn structure_ids() {
+        let turret = TurretId::parse("Turret_T2_L_03_A").unwrap();
+        assert_eq!(turret.team, Team::Chaos);
+        assert_eq!(turret.lane, Lane::Top);
+        assert_eq!(turret.tier, TurretTier::Outer);
+
+        let turret = TurretId::parse("Turret_T1_C_05_A").unwrap();
+        assert_eq!((turret.lane, turret.tier), (Lane::Mid, TurretTier::Outer));
+        let turret = TurretId::parse("Turret_T1_R_01_A").unwrap();
+        assert_eq!((turret.lane, turret.tier), (Lane::Bot, TurretTier::Base));
+        let turret = TurretId::parse("Turret_T1_C_02_A").unwrap();
+        assert_eq!((turret.tier, turret.number), (TurretTier::Nexus, 2));
+        assert_eq!(TurretId::parse("Turret_T1_C_07_A"), None);
+        assert_eq!(TurretId::parse("Barracks_T1_C1"), None);
+
+        let inhib = InhibitorId::parse("Barracks_T1_C1").unwrap();
+        assert_eq!(inhib.team, Team::Order);
+        assert_eq!(inhib.lane, Lane::Mid);
+        assert_eq!(InhibitorId::parse("Barracks_T3_C1"), None);
+    }
+
+    #[test]
+    fn event_turret() {
+        let event_str = r#"{
+            "EventID": 4,
+            "EventName": "TurretKilled",
+            "EventTime": 610.2,
+            "KillerName": "Riot Tuxedo",
+            "TurretKilled": "Turret_T2_C_04_A",
+            "Assisters": []
+        }"#;
+        let e: GameEvent = serde_json::from_str(event_str).unwrap();
+        let turret = e.event_type.turret().unwrap();
+        assert_eq!(turret.team, Team::Chaos);
+        assert_eq!(turret.tier, TurretTier::Inner);
+        assert_eq!(e.event_type.inhibitor(), None);
+    }
+
+    #[test]
+    fn event_game_end() {
+        let event_str = r#"{
+            "EventID": 42,
+            "EventName": "GameEnd",
+            "EventTime": 1843.2,
+            "Result": "Win"
+        }"#;
+        let e: GameEvent = serde_json::from_str(event_str).unwrap();
+        match e.event_type {
+            GameEventType::GameEnd { result } => assert_eq!(result, GameResult::Win),
+            _ => panic!("Did not match GameEnd"),
+        }
+    }
+
+    #[test]
+    fn event_horde() {
+        let event_str = r#"{
+            "EventID": 3,
+            "EventName": "HordeKill",
+            "EventTime": 372.1,
+            "KillerName": "Riot Tuxedo",
+            "Stolen": "True",
+            "Assisters": []
+        }"#;
+        let e: GameEvent = serde_json::from_str(event_str).unwrap();
+        match e.event_type {
+            GameEventType::HordeKill { stolen, .. } => assert!(stolen),
+            _ => panic!("Did not match HordeKill"),
+        }
+    }
+
     #[test]
     fn all_events() {
         let event_list_str = r#"{
@@ -599,6 +1878,143 @@ mod tests {
         assert_eq!(allevents.events.len(), 15);
     }
 
+    #[test]
+    fn all_events_current_patch() {
+        let event_list_str = r#"{
+            "Events": [
+                {
+                    "EventID": 0,
+                    "EventName": "GameStart",
+                    "EventTime": 0.0
+                },
+                {
+                    "EventID": 1,
+                    "EventName": "ChampionKill",
+                    "EventTime": 201.7,
+                    "KillerName": "Riot Tuxedo",
+                    "VictimName": "Lux Bot",
+                    "Assisters": []
+                },
+                {
+                    "EventID": 2,
+                    "EventName": "FirstBlood",
+                    "EventTime": 201.7,
+                    "Recipient": "Riot Tuxedo"
+                },
+                {
+                    "EventID": 3,
+                    "EventName": "HordeKill",
+                    "EventTime": 372.1,
+                    "KillerName": "Riot Tuxedo",
+                    "Stolen": "False",
+                    "Assisters": []
+                },
+                {
+                    "EventID": 4,
+                    "EventName": "InhibKilled",
+                    "EventTime": 1402.5,
+                    "KillerName": "Riot Tuxedo",
+                    "InhibKilled": "Barracks_T2_C1",
+                    "Assisters": []
+                },
+                {
+                    "EventID": 5,
+                    "EventName": "InhibRespawningSoon",
+                    "EventTime": 1687.5,
+                    "InhibRespawningSoon": "Barracks_T2_C1"
+                },
+                {
+                    "EventID": 6,
+                    "EventName": "InhibRespawned",
+                    "EventTime": 1702.5,
+                    "InhibRespawned": "Barracks_T2_C1"
+                },
+                {
+                    "EventID": 7,
+                    "EventName": "GameEnd",
+                    "EventTime": 1843.2,
+                    "Result": "Lose"
+                }
+            ]
+        }"#;
+        let allevents: GameEventAll = serde_json::from_str(event_list_str).unwrap();
+        assert_eq!(allevents.events.len(), 8);
+        assert!(allevents.skipped.is_empty());
+        assert!(allevents
+            .events
+            .iter()
+            .all(|e| !matches!(e.event_type, GameEventType::Unknown { .. })));
+        match &allevents.events[2].event_type {
+            GameEventType::FirstBlood { recipient } => assert_eq!(recipient, "Riot Tuxedo"),
+            _ => panic!("Did not match FirstBlood"),
+        }
+        match &allevents.events[5].event_type {
+            GameEventType::InhibRespawningSoon {
+                inhib_respawning_soon,
+            } => assert_eq!(inhib_respawning_soon, "Barracks_T2_C1"),
+            _ => panic!("Did not match InhibRespawningSoon"),
+        }
+        let inhib = allevents.events[6].event_type.inhibitor().unwrap();
+        assert_eq!((inhib.team, inhib.lane), (Team::Chaos, Lane::Mid));
+        match &allevents.events[7].event_type {
+            GameEventType::GameEnd { result } => assert_eq!(*result, GameResult::Lose),
+            _ => panic!("Did not match GameEnd"),
+        }
+    }
+
+    #[test]
+    fn event_unknown() {
+        let event_str = r#"{
+            "EventID": 7,
+            "EventName": "FeatsOfStrength",
+            "EventTime": 812.4,
+            "KillerName": "Riot Tuxedo"
+        }"#;
+        let e: GameEvent = serde_json::from_str(event_str).unwrap();
+        assert_eq!(e.event_id, 7);
+        assert_eq!(e.event_type.name(), "FeatsOfStrength");
+        match e.event_type {
+            GameEventType::Unknown { ref raw, .. } => {
+                assert_eq!(raw["KillerName"], "Riot Tuxedo");
+            }
+            _ => panic!("Did not match Unknown"),
+        }
+
+        let value = serde_json::to_value(&e).unwrap();
+        assert_eq!(value["EventName"], "FeatsOfStrength");
+        assert_eq!(value["EventID"], 7);
+    }
+
+    #[test]
+    fn all_events_skipped() {
+        let event_list_str = r#"{
+            "Events": [
+                {
+                    "EventID": 0,
+                    "EventName": "GameStart",
+                    "EventTime": 0.0
+                },
+                {
+                    "EventID": 1,
+                    "EventName": "ChampionKill",
+                    "EventTime": 154.4,
+                    "KillerName": "Riot Tuxedo"
+                },
+                {
+                    "EventID": 2,
+                    "EventName": "FeatsOfStrength",
+                    "EventTime": 812.4
+                }
+            ]
+        }"#;
+        let allevents: GameEventAll = serde_json::from_str(event_list_str).unwrap();
+        assert_eq!(allevents.events.len(), 2);
+        assert_eq!(allevents.skipped.len(), 1);
+        assert_eq!(allevents.skipped[0].index, 1);
+        assert!(allevents.skipped[0].error.contains("VictimName"));
+        assert_eq!(allevents.skipped[0].raw["EventID"], 1);
+    }
+
     #[test]
     fn main_runes() {
         let mainrunes_str = r#"{
@@ -625,6 +2041,25 @@ mod tests {
         assert_eq!(mr.keystone.display_name, "Electrocute");
     }
 
+    #[test]
+    fn summoner_spells() {
+        let spells_str = r#"{
+            "summonerSpellOne": {
+                "displayName": "Flash",
+                "rawDescription": "GeneratedTip_SummonerSpell_SummonerFlash_Description",
+                "rawDisplayName": "GeneratedTip_SummonerSpell_SummonerFlash_DisplayName"
+            },
+            "summonerSpellTwo": {
+                "displayName": "Ignite",
+                "rawDescription": "GeneratedTip_SummonerSpell_SummonerDot_Description",
+                "rawDisplayName": "GeneratedTip_SummonerSpell_SummonerDot_DisplayName"
+            }
+        }"#;
+        let ss: SummonerSpells = serde_json::from_str(spells_str).unwrap();
+        assert_eq!(ss.summoner_spell_one.unwrap().display_name, "Flash");
+        assert_eq!(ss.summoner_spell_two.unwrap().display_name, "Ignite");
+    }
+
     #[test]
     fn active_runes() {
         let activerunes_str = r#"{
@@ -746,6 +2181,59 @@ mod tests {
         let apa: ActivePlayerAbilities = serde_json::from_str(abilities_str).unwrap();
 
         assert_eq!(apa.abilities.len(), 5);
+        assert_eq!(apa.r().unwrap().display_name, "Summon: Tibbers");
+        assert!(apa.passive().unwrap().ability_level.is_none());
+        assert_eq!(apa.points_spent(), 0);
+        assert_eq!(apa.suggest_level_up(1), Some(AbilitySlot::Q));
+    }
+
+    fn abilities(levels: [i32; 4]) -> ActivePlayerAbilities {
+        let abilities = AbilitySlot::LEVELED
+            .iter()
+            .zip(levels)
+            .map(|(slot, level)| {
+                let ability = Ability {
+                    ability_level: Some(level),
+                    display_name: slot.to_string(),
+                    id: slot.to_string(),
+                    raw_description: String::new(),
+                    raw_display_name: String::new(),
+                };
+                (slot.clone(), ability)
+            })
+            .collect();
+        ActivePlayerAbilities { abilities }
+    }
+
+    #[test]
+    fn level_ups() {
+        let apa = abilities([3, 1, 1, 0]);
+        assert_eq!(apa.points_spent(), 5);
+        assert!(apa.level_ups(5).is_empty());
+        assert_eq!(apa.suggest_level_up(6), Some(AbilitySlot::R));
+        // rank 4 needs champion level 7
+        assert_eq!(
+            apa.level_ups(6),
+            vec![AbilitySlot::W, AbilitySlot::E, AbilitySlot::R]
+        );
+
+        let apa = abilities([3, 1, 1, 1]);
+        assert_eq!(apa.suggest_level_up(6), None);
+        assert_eq!(apa.suggest_level_up(7), Some(AbilitySlot::Q));
+        assert_eq!(
+            abilities([1, 1, 1, 0]).suggest_level_up(4),
+            Some(AbilitySlot::Q)
+        );
+
+        let apa = abilities([5, 5, 5, 3]);
+        assert_eq!(apa.suggest_level_up(18), None);
+
+        let other: ActivePlayerAbilities =
+            serde_json::from_str(r#"{"Extra": {"displayName": "", "id": "", "rawDescription": "", "rawDisplayName": ""}}"#)
+                .unwrap();
+        assert!(other
+            .get(&AbilitySlot::Other("Extra".to_string()))
+            .is_some());
     }
 
     #[test]
@@ -898,6 +2386,7 @@ mod tests {
 
         assert_eq!(ap.summoner_name, "Riot Tuxedo");
         assert_eq!(ap.full_runes.general_runes.len(), 6);
+        assert_eq!(ap.champion_stats.resource_type, ResourceType::Mana);
     }
 
     #[test]
@@ -958,5 +2447,219 @@ mod tests {
         let p: Player = serde_json::from_str(player_str).unwrap();
 
         assert_eq!(p.summoner_name, "Riot Tuxedo");
+        assert_eq!(p.team, Team::Order);
+        assert_eq!(p.position, None);
+        assert_eq!(
+            p.player_id(),
+            PlayerId::SummonerName("Riot Tuxedo".to_string())
+        );
+
+        let value = serde_json::to_value(&p).unwrap();
+        assert_eq!(value["team"], "ORDER");
+        assert_eq!(value["position"], "");
+    }
+
+    #[test]
+    fn string_enums() {
+        let position: Position = serde_json::from_str(r#""UTILITY""#).unwrap();
+        assert_eq!(position, Position::Utility);
+        let dragon: DragonType = serde_json::from_str(r#""Elder""#).unwrap();
+        assert_eq!(dragon, DragonType::Elder);
+
+        let resource: ResourceType = serde_json::from_str(r#""COURAGE""#).unwrap();
+        assert_eq!(resource, ResourceType::Other("COURAGE".to_string()));
+        assert_eq!(serde_json::to_string(&resource).unwrap(), r#""COURAGE""#);
+        assert_eq!(Team::Chaos.to_string(), "CHAOS");
+    }
+
+    #[test]
+    fn render_settings() {
+        let render_str = include_str!("../../fixtures/replay/render.json");
+        let render: RenderSettings = serde_json::from_str(render_str).unwrap();
+        assert_eq!(render.camera_mode, CameraMode::Top);
+        assert_eq!(render.camera_position.x, 7430.5);
+        assert_eq!(render.extra["depthOfFieldFar"], 5000.0);
+
+        let value = serde_json::to_value(&render).unwrap();
+        let original: Value = serde_json::from_str(render_str).unwrap();
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn render_settings_partial() {
+        let render: RenderSettings =
+            serde_json::from_str(r#"{"cameraMode": "fps", "fogOfWar": true}"#).unwrap();
+        assert_eq!(render.camera_mode, CameraMode::Fps);
+        assert!(render.fog_of_war);
+        assert!(!render.interface_all);
+        assert!(render.extra.is_empty());
+    }
+
+    #[test]
+    fn render_update_diff() {
+        let current: RenderSettings =
+            serde_json::from_str(include_str!("../../fixtures/replay/render.json")).unwrap();
+        let mut changed = current.clone();
+        changed.camera_mode = CameraMode::Fps;
+        changed.fog_of_war = false;
+        changed
+            .extra
+            .insert("depthFogEnabled".to_string(), json!(true));
+
+        let update = RenderUpdate::diff(&current, &changed);
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({"cameraMode": "fps", "fogOfWar": false, "depthFogEnabled": true})
+        );
+        assert!(RenderUpdate::diff(&current, &current).is_empty());
+
+        let update = RenderUpdate::new()
+            .with_camera_position(Vector3::new(1.0, 2.0, 3.0))
+            .with_selection_name("Annie");
+        assert_eq!(update.camera_position, Some(Vector3::new(1.0, 2.0, 3.0)));
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({"cameraPosition": {"x": 1.0, "y": 2.0, "z": 3.0}, "selectionName": "Annie"})
+        );
+
+        let update = RenderUpdate::new()
+            .with_camera_move_speed(500.0)
+            .with_health_bar_minions(false)
+            .with_interface_timeline(false);
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({"cameraMoveSpeed": 500.0, "healthBarMinions": false, "interfaceTimeline": false})
+        );
+    }
+
+    #[test]
+    fn all_game_data() {
+        let all_game_data_str = r#"{
+            "activePlayer": {
+                "abilities": {
+                    "Passive": {
+                        "displayName": "Pyromania",
+                        "id": "AnniePassive",
+                        "rawDescription": "GeneratedTip_Passive_AnniePassive_Description",
+                        "rawDisplayName": "GeneratedTip_Passive_AnniePassive_DisplayName"
+                    }
+                },
+                "championStats": {
+                    "armor": 0,
+                    "armorPenetrationFlat": 0,
+                    "armorPenetrationPercent": 0,
+                    "attackDamage": 0,
+                    "attackRange": 0,
+                    "attackSpeed": 0,
+                    "bonusArmorPenetrationPercent": 0,
+                    "bonusMagicPenetrationPercent": 0,
+                    "critChance": 0,
+                    "critDamage": 0,
+                    "currentHealth": 0,
+                    "healthRegenRate": 0,
+                    "maxHealth": 0,
+                    "moveSpeed": 0,
+                    "resourceMax": 0,
+                    "resourceRegenRate": 0,
+                    "resourceType": "MANA",
+                    "resourceValue": 0
+                },
+                "currentGold": 500,
+                "fullRunes": {
+                    "generalRunes": [],
+                    "keystone": {
+                        "displayName": "Electrocute",
+                        "id": 8112,
+                        "rawDescription": "perk_tooltip_Electrocute",
+                        "rawDisplayName": "perk_displayname_Electrocute"
+                    },
+                    "primaryRuneTree": {
+                        "displayName": "Domination",
+                        "id": 8100,
+                        "rawDescription": "perkstyle_tooltip_7200",
+                        "rawDisplayName": "perkstyle_displayname_7200"
+                    },
+                    "secondaryRuneTree": {
+                        "displayName": "Sorcery",
+                        "id": 8200,
+                        "rawDescription": "perkstyle_tooltip_7202",
+                        "rawDisplayName": "perkstyle_displayname_7202"
+                    },
+                    "statRunes": []
+                },
+                "level": 1,
+                "summonerName": "Riot Tuxedo"
+            },
+            "allPlayers": [
+                {
+                    "championName": "Annie",
+                    "isBot": false,
+                    "isDead": false,
+                    "items": [],
+                    "level": 1,
+                    "position": "",
+                    "rawChampionName": "game_character_displayname_Annie",
+                    "respawnTimer": 0,
+                    "scores": {
+                        "assists": 0,
+                        "creepScore": 0,
+                        "deaths": 0,
+                        "kills": 0,
+                        "wardScore": 0
+                    },
+                    "skinID": 0,
+                    "summonerName": "Riot Tuxedo",
+                    "summonerSpells": {},
+                    "team": "ORDER"
+                }
+            ],
+            "events": {
+                "Events": [
+                    {
+                        "EventID": 0,
+                        "EventName": "GameStart",
+                        "EventTime": 0.0563616007566452
+                    }
+                ]
+            },
+            "gameData": {
+                "gameMode": "CLASSIC",
+                "gameTime": 80.5,
+                "mapName": "Map11",
+                "mapNumber": 11,
+                "mapTerrain": "Default"
+            }
+        }"#;
+
+        let data: AllGameData = serde_json::from_str(all_game_data_str).unwrap();
+
+        assert_eq!(data.active_player.unwrap().current_gold, 500.0);
+        assert_eq!(data.all_players.len(), 1);
+        assert_eq!(data.events.events.len(), 1);
+        assert_eq!(data.game_data.game_mode, "CLASSIC");
+    }
+
+    #[test]
+    fn all_game_data_spectator() {
+        let all_game_data_str = r#"{
+            "activePlayer": {
+                "error": "Spectator mode doesn't currently support this feature"
+            },
+            "allPlayers": [],
+            "events": {
+                "Events": []
+            },
+            "gameData": {
+                "gameMode": "CLASSIC",
+                "gameTime": 0,
+                "mapName": "Map11",
+                "mapNumber": 11,
+                "mapTerrain": "Default"
+            }
+        }"#;
+
+        let data: AllGameData = serde_json::from_str(all_game_data_str).unwrap();
+
+        assert!(data.active_player.is_none());
     }
 }
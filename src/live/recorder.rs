@@ -0,0 +1,505 @@
+//! Records polls of the Live Client Data API to newline-delimited JSON, so a whole game
+//! can be archived next to other logs and replayed later.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::live::client::LiveClient;
+use crate::live::models::{Player, PlayerId};
+
+/// Endpoints polled on every tick, in order.
+pub const ENDPOINTS: &[&str] = &[
+    "/liveclientdata/gamestats",
+    "/liveclientdata/eventdata",
+    "/liveclientdata/playerlist",
+    "/liveclientdata/activeplayer",
+    "/liveclientdata/activeplayername",
+    "/liveclientdata/activeplayerabilities",
+    "/liveclientdata/activeplayerrunes",
+    "/liveclientdata/allgamedata",
+];
+
+/// Endpoints polled for every player in the last player list.
+pub const PLAYER_ENDPOINTS: &[&str] = &[
+    "/liveclientdata/playerscores",
+    "/liveclientdata/playeritems",
+    "/liveclientdata/playersummonerspells",
+    "/liveclientdata/playermainrunes",
+];
+
+/// One recorded request, a line of the session file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordEntry {
+    /// Unix time of the request in milliseconds.
+    pub timestamp: u64,
+    /// Milliseconds since the recording started.
+    pub elapsed: u64,
+    /// Index of the poll this request was part of.
+    #[serde(default)]
+    pub poll: u64,
+    /// Path and query of the request.
+    pub path: String,
+    /// `None` when no response was received.
+    pub status: Option<u16>,
+    pub latency_ms: f64,
+    /// The response body, `null` when there was none or it is not JSON.
+    pub body: Value,
+    /// The response body when it is not JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Why no response was received.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecorderConfig {
+    pub interval: Duration,
+    /// Also poll the per-player endpoints for every player in the player list.
+    pub per_player: bool,
+    /// Starts a new file once the current one holds this many entries.
+    pub max_entries_per_file: Option<u64>,
+    /// Starts a new file once this many uncompressed bytes went into the current one.
+    pub max_bytes_per_file: Option<u64>,
+    pub gzip: bool,
+}
+
+impl RecorderConfig {
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn new() -> Self {
+        Self {
+            interval: Self::DEFAULT_INTERVAL,
+            per_player: true,
+            max_entries_per_file: None,
+            max_bytes_per_file: None,
+            gzip: false,
+        }
+    }
+}
+
+impl Default for RecorderConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Path of the `index`th file of a session, `game.ndjson` then `game.1.ndjson` and so on.
+pub fn session_file(path: &Path, index: u32, gzip: bool) -> PathBuf {
+    let mut name = match (index, path.file_stem(), path.extension()) {
+        (0, _, _) | (_, None, _) => path.as_os_str().to_owned(),
+        (_, Some(stem), Some(ext)) => path
+            .with_file_name(format!(
+                "{}.{}.{}",
+                stem.to_string_lossy(),
+                index,
+                ext.to_string_lossy()
+            ))
+            .into_os_string(),
+        (_, Some(stem), None) => path
+            .with_file_name(format!("{}.{}", stem.to_string_lossy(), index))
+            .into_os_string(),
+    };
+    if gzip {
+        name.push(".gz");
+    }
+    PathBuf::from(name)
+}
+
+enum Output {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl Output {
+    fn create(path: &Path, gzip: bool) -> io::Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        Ok(if gzip {
+            Output::Gzip(GzEncoder::new(file, Compression::default()))
+        } else {
+            Output::Plain(file)
+        })
+    }
+
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            Output::Plain(file) => file,
+            Output::Gzip(encoder) => encoder,
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Output::Plain(mut file) => file.flush(),
+            Output::Gzip(encoder) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+/// Writes entries to the session files, rotating them as configured.
+struct SessionWriter {
+    path: PathBuf,
+    gzip: bool,
+    max_entries: Option<u64>,
+    max_bytes: Option<u64>,
+    output: Option<Output>,
+    entries: u64,
+    bytes: u64,
+    files: Vec<PathBuf>,
+}
+
+impl SessionWriter {
+    fn new(path: PathBuf, config: &RecorderConfig) -> Self {
+        Self {
+            path,
+            gzip: config.gzip,
+            max_entries: config.max_entries_per_file,
+            max_bytes: config.max_bytes_per_file,
+            output: None,
+            entries: 0,
+            bytes: 0,
+            files: Vec::new(),
+        }
+    }
+
+    fn write(&mut self, entry: &RecordEntry) -> io::Result<()> {
+        let full = self.max_entries.is_some_and(|max| self.entries >= max)
+            || self.max_bytes.is_some_and(|max| self.bytes >= max);
+        if full {
+            if let Some(output) = self.output.take() {
+                output.finish()?;
+            }
+        }
+        let output = match &mut self.output {
+            Some(output) => output,
+            None => {
+                let path = session_file(&self.path, self.files.len() as u32, self.gzip);
+                if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                    fs::create_dir_all(dir)?;
+                }
+                let output = Output::create(&path, self.gzip)?;
+                self.files.push(path);
+                self.entries = 0;
+                self.bytes = 0;
+                self.output.insert(output)
+            }
+        };
+
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        output.writer().write_all(&line)?;
+        self.entries += 1;
+        self.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.output {
+            Some(output) => output.writer().flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn finish(mut self) -> io::Result<Vec<PathBuf>> {
+        if let Some(output) = self.output.take() {
+            output.finish()?;
+        }
+        Ok(self.files)
+    }
+}
+
+/// Polls every endpoint of a [`LiveClient`] and writes the raw responses to a session file.
+pub struct Recorder<'a> {
+    client: &'a LiveClient,
+    config: RecorderConfig,
+    writer: SessionWriter,
+    started: Instant,
+    polls: u64,
+    players: Vec<PlayerId>,
+}
+
+impl<'a> Recorder<'a> {
+    /// Records to `path`, later files of a rotated session are named by [`session_file`].
+    pub fn new(client: &'a LiveClient, path: impl Into<PathBuf>, config: RecorderConfig) -> Self {
+        Self {
+            client,
+            writer: SessionWriter::new(path.into(), &config),
+            config,
+            started: Instant::now(),
+            polls: 0,
+            players: Vec::new(),
+        }
+    }
+
+    /// Polls every endpoint once. Returns whether the game client answered at all.
+    pub async fn poll(&mut self) -> io::Result<bool> {
+        let mut answered = false;
+        for path in ENDPOINTS {
+            answered |= self.record(path).await?;
+        }
+        if self.config.per_player {
+            for player in self.players.clone() {
+                for endpoint in PLAYER_ENDPOINTS {
+                    answered |= self.record(&player.path(endpoint)).await?;
+                }
+            }
+        }
+        self.writer.flush()?;
+        self.polls += 1;
+        Ok(answered)
+    }
+
+    /// Polls at the configured interval until the game client goes away, then finishes
+    /// the session. Nothing is written while waiting for the game client to answer, so
+    /// an idle recorder does not fill the session with connection errors.
+    pub async fn run(mut self) -> io::Result<Vec<PathBuf>> {
+        while !self.probe().await {
+            tokio::time::sleep(self.config.interval).await;
+        }
+        self.started = Instant::now();
+        loop {
+            let start = Instant::now();
+            if !self.poll().await? {
+                break;
+            }
+            tokio::time::sleep(self.config.interval.saturating_sub(start.elapsed())).await;
+        }
+        self.finish()
+    }
+
+    /// Flushes and closes the session, returning the files written.
+    pub fn finish(self) -> io::Result<Vec<PathBuf>> {
+        self.writer.finish()
+    }
+
+    /// Whether the game client answers, without recording the request.
+    async fn probe(&self) -> bool {
+        let request = self.client.request(Method::GET, ENDPOINTS[0]);
+        self.client.send(&request).await.is_ok()
+    }
+
+    async fn record(&mut self, path: &str) -> io::Result<bool> {
+        let request = self.client.request(Method::GET, path);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let elapsed = self.started.elapsed().as_millis() as u64;
+        let start = Instant::now();
+        let response = self.client.send(&request).await;
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let entry = match response {
+            Ok(response) => {
+                let (body, text) = match serde_json::from_slice(&response.body) {
+                    Ok(body) => (body, None),
+                    Err(_) => (
+                        Value::Null,
+                        Some(String::from_utf8_lossy(&response.body).into_owned()),
+                    ),
+                };
+                RecordEntry {
+                    timestamp,
+                    elapsed,
+                    poll: self.polls,
+                    path: path.to_string(),
+                    status: Some(response.status.as_u16()),
+                    latency_ms,
+                    body,
+                    text,
+                    error: None,
+                }
+            }
+            Err(e) => RecordEntry {
+                timestamp,
+                elapsed,
+                poll: self.polls,
+                path: path.to_string(),
+                status: None,
+                latency_ms,
+                body: Value::Null,
+                text: None,
+                error: Some(e.to_string()),
+            },
+        };
+        if path == "/liveclientdata/playerlist" && entry.status == Some(200) {
+            if let Ok(players) = Vec::<Player>::deserialize(&entry.body) {
+                self.players = players.iter().map(Player::player_id).collect();
+            }
+        }
+        self.writer.write(&entry)?;
+        Ok(entry.status.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::cert::CertificateSource;
+    use crate::live::config::LiveClientConfig;
+    use crate::live::mock::{MockGame, MockServer};
+    use crate::live::retry::RetryPolicy;
+    use flate2::read::GzDecoder;
+    use std::io::{BufRead, BufReader, Read};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rumble-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn read_entries(path: &Path) -> Vec<RecordEntry> {
+        let file = File::open(path).unwrap();
+        let reader: Box<dyn Read> = if path.extension().is_some_and(|ext| ext == "gz") {
+            Box::new(GzDecoder::new(file))
+        } else {
+            Box::new(file)
+        };
+        BufReader::new(reader)
+            .lines()
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn session_files() {
+        let path = Path::new("games/game.ndjson");
+        assert_eq!(session_file(path, 0, false), path);
+        assert_eq!(
+            session_file(path, 2, true),
+            Path::new("games/game.2.ndjson.gz")
+        );
+        assert_eq!(
+            session_file(Path::new("game"), 1, false),
+            Path::new("game.1")
+        );
+    }
+
+    #[tokio::test]
+    async fn record() {
+        let server = MockServer::start(MockGame::new()).await.unwrap();
+        let lcu = LiveClient::new(server.config());
+        let dir = temp_dir("record");
+
+        let mut recorder = Recorder::new(&lcu, dir.join("game.ndjson"), RecorderConfig::new());
+        assert!(recorder.poll().await.unwrap());
+        assert!(recorder.poll().await.unwrap());
+        let files = recorder.finish().unwrap();
+        assert_eq!(files.len(), 1);
+
+        let entries = read_entries(&files[0]);
+        assert_eq!(
+            entries.len(),
+            (ENDPOINTS.len() + PLAYER_ENDPOINTS.len() * 2) * 2
+        );
+        assert_eq!(entries[0].path, "/liveclientdata/gamestats");
+        assert_eq!(entries[0].status, Some(200));
+        assert_eq!(entries[0].body["gameMode"], "CLASSIC");
+        let scores = entries
+            .iter()
+            .find(|e| e.path == "/liveclientdata/playerscores?summonerName=Lux%20Bot")
+            .unwrap();
+        assert_eq!(scores.body["kills"], 0);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn record_rotated_gzip() {
+        let server = MockServer::start(MockGame::new()).await.unwrap();
+        let lcu = LiveClient::new(server.config());
+        let dir = temp_dir("rotated");
+        let config = RecorderConfig {
+            per_player: false,
+            max_entries_per_file: Some(5),
+            gzip: true,
+            ..RecorderConfig::new()
+        };
+
+        let mut recorder = Recorder::new(&lcu, dir.join("game.ndjson"), config);
+        recorder.poll().await.unwrap();
+        recorder.poll().await.unwrap();
+        let files = recorder.finish().unwrap();
+        assert_eq!(files.len(), 4);
+        assert_eq!(files[1], dir.join("game.1.ndjson.gz"));
+
+        let entries: Vec<RecordEntry> = files.iter().flat_map(|f| read_entries(f)).collect();
+        assert_eq!(entries.len(), ENDPOINTS.len() * 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_until_game_closes() {
+        let server = MockServer::start(MockGame::new()).await.unwrap();
+        let lcu = LiveClient::new(server.config());
+        let dir = temp_dir("run");
+        let config = RecorderConfig {
+            interval: Duration::from_millis(20),
+            per_player: false,
+            ..RecorderConfig::new()
+        };
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            server.shutdown().await;
+        });
+        let files = Recorder::new(&lcu, dir.join("game.ndjson"), config)
+            .run()
+            .await
+            .unwrap();
+
+        let entries = read_entries(&files[0]);
+        assert!(entries.iter().any(|e| e.status == Some(200)));
+        assert!(entries.last().unwrap().error.is_some());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_waits_silently_for_game() {
+        // reserve a port nothing listens on until the game starts
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let client_config = LiveClientConfig::builder()
+            .base_url(format!("https://{}", addr))
+            .retry_policy(RetryPolicy::none())
+            .certificate(CertificateSource::InsecureForTests)
+            .build()
+            .unwrap();
+        let lcu = LiveClient::new(client_config);
+        let dir = temp_dir("idle");
+        let config = RecorderConfig {
+            interval: Duration::from_millis(20),
+            per_player: false,
+            ..RecorderConfig::new()
+        };
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            let server = MockServer::bind(addr, MockGame::new()).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            server.shutdown().await;
+        });
+        let files = Recorder::new(&lcu, dir.join("game.ndjson"), config)
+            .run()
+            .await
+            .unwrap();
+
+        let entries = read_entries(&files[0]);
+        assert_eq!(entries[0].status, Some(200));
+        assert_eq!(entries[0].poll, 0);
+        assert!(entries[0].elapsed < 100);
+        // only the polls noticing the game closed have errors
+        let failed = entries.iter().filter(|e| e.error.is_some()).count();
+        assert!(failed <= ENDPOINTS.len() * 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
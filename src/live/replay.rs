@@ -0,0 +1,390 @@
+//! Feeds a session written by the [`Recorder`](crate::live::recorder::Recorder) back through
+//! the endpoint apis, so code built on them can be tested against real games.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use flate2::read::GzDecoder;
+use futures::future::BoxFuture;
+use reqwest::{StatusCode, Url};
+use serde_json::{json, Value};
+
+use crate::live::error::{LiveClientError, LiveClientResult};
+use crate::live::recorder::{session_file, RecordEntry};
+use crate::live::transport::{Transport, TransportRequest, TransportResponse};
+
+/// The entries of a recorded session, ordered by time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    entries: Vec<RecordEntry>,
+    /// Time at which each poll finished, in milliseconds.
+    polls: Vec<u64>,
+}
+
+impl Recording {
+    pub fn new(mut entries: Vec<RecordEntry>) -> Self {
+        entries.sort_by_key(|e| e.elapsed);
+        let mut polls: Vec<(u64, u64)> = Vec::new();
+        for entry in &entries {
+            match polls.iter_mut().find(|(poll, _)| *poll == entry.poll) {
+                Some((_, end)) => *end = (*end).max(entry.elapsed),
+                None => polls.push((entry.poll, entry.elapsed)),
+            }
+        }
+        let mut polls: Vec<u64> = polls.into_iter().map(|(_, end)| end).collect();
+        polls.sort_unstable();
+        Self { entries, polls }
+    }
+
+    /// Reads a session and every rotated file after it, gzipped or not.
+    ///
+    /// `game.ndjson` also finds a gzipped `game.ndjson.gz` session.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let (base, gzip) = match path.to_str().and_then(|p| p.strip_suffix(".gz")) {
+            Some(base) => (PathBuf::from(base), true),
+            None => (
+                path.to_path_buf(),
+                !path.exists() && session_file(path, 0, true).exists(),
+            ),
+        };
+
+        let mut entries = Vec::new();
+        for index in 0.. {
+            let file = session_file(&base, index, gzip);
+            if index > 0 && !file.exists() {
+                break;
+            }
+            let file = File::open(file)?;
+            if gzip {
+                entries.extend(Self::read(GzDecoder::new(file))?);
+            } else {
+                entries.extend(Self::read(file)?);
+            }
+        }
+        Ok(Self::new(entries))
+    }
+
+    /// Reads newline-delimited entries, skipping blank lines.
+    pub fn read(reader: impl Read) -> io::Result<Vec<RecordEntry>> {
+        let mut entries = Vec::new();
+        for line in BufReader::new(reader).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(entries)
+    }
+
+    pub fn entries(&self) -> &[RecordEntry] {
+        &self.entries
+    }
+
+    /// Time of the last recorded request.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.entries.last().map_or(0, |e| e.elapsed))
+    }
+
+    /// Latest entry recorded for `path` at `position` milliseconds. Falls back to the
+    /// path without its query, like the game client ignores unknown parameters.
+    fn entry(&self, path: &str, position: u64) -> Option<&RecordEntry> {
+        let recorded = &self.entries[..self.entries.partition_point(|e| e.elapsed <= position)];
+        let without_query = path.split('?').next().unwrap_or(path);
+        recorded
+            .iter()
+            .rev()
+            .find(|e| e.path == path)
+            .or_else(|| recorded.iter().rev().find(|e| e.path == without_query))
+    }
+}
+
+/// How replayed time moves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Playback {
+    /// As fast as the session was recorded.
+    Original,
+    /// Faster or slower than recorded, e.g. `10.0` replays a minute in six seconds.
+    Accelerated(f64),
+    /// Stands still at the first poll until moved with [`ReplayTransport::step`] or
+    /// [`ReplayTransport::seek`].
+    Step,
+}
+
+impl Playback {
+    fn speed(&self) -> Option<f64> {
+        match self {
+            Playback::Original => Some(1.0),
+            Playback::Accelerated(speed) => Some(*speed),
+            Playback::Step => None,
+        }
+    }
+}
+
+struct Clock {
+    position: Duration,
+    resumed: Option<Instant>,
+}
+
+/// Answers requests with the responses recorded at the current replay position.
+///
+/// Requests are served the latest response recorded for their path. Paths that were not
+/// recorded yet and polls where the game client was not reachable fail like a closed game.
+pub struct ReplayTransport {
+    recording: Recording,
+    playback: Playback,
+    clock: Mutex<Clock>,
+}
+
+impl ReplayTransport {
+    pub fn new(recording: Recording, playback: Playback) -> Self {
+        let clock = match playback.speed() {
+            Some(_) => Clock {
+                position: Duration::ZERO,
+                resumed: Some(Instant::now()),
+            },
+            None => Clock {
+                position: Duration::from_millis(recording.polls.first().copied().unwrap_or(0)),
+                resumed: None,
+            },
+        };
+        Self {
+            recording,
+            playback,
+            clock: Mutex::new(clock),
+        }
+    }
+
+    pub fn open(path: impl AsRef<Path>, playback: Playback) -> io::Result<Self> {
+        Ok(Self::new(Recording::open(path)?, playback))
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Time into the recording.
+    pub fn position(&self) -> Duration {
+        let clock = self.clock.lock().unwrap();
+        match (clock.resumed, self.playback.speed()) {
+            (Some(resumed), Some(speed)) => clock.position + resumed.elapsed().mul_f64(speed),
+            _ => clock.position,
+        }
+    }
+
+    /// Moves to `position`, timed playback continues from there.
+    pub fn seek(&self, position: Duration) {
+        let mut clock = self.clock.lock().unwrap();
+        clock.position = position;
+        if clock.resumed.is_some() {
+            clock.resumed = Some(Instant::now());
+        }
+    }
+
+    /// Moves to the end of the next recorded poll. Returns `false` at the end of the recording.
+    pub fn step(&self) -> bool {
+        let position = self.position().as_millis() as u64;
+        match self.recording.polls.iter().find(|end| **end > position) {
+            Some(end) => {
+                self.seek(Duration::from_millis(*end));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether every recorded request has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.position() >= self.recording.duration()
+    }
+
+    fn response(&self, path: &str) -> LiveClientResult<TransportResponse> {
+        let position = self.position().as_millis() as u64;
+        let entry =
+            self.recording
+                .entry(path, position)
+                .ok_or_else(|| LiveClientError::Disconnected {
+                    message: format!("nothing recorded for {} yet", path),
+                    retries: 0,
+                })?;
+        let status = entry.status.ok_or_else(|| LiveClientError::Disconnected {
+            message: entry
+                .error
+                .clone()
+                .unwrap_or_else(|| "no response recorded".to_string()),
+            retries: 0,
+        })?;
+        let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = match &entry.text {
+            Some(text) => text.clone().into_bytes(),
+            None if entry.path != path => since_event(&entry.body, path).to_string().into_bytes(),
+            None => entry.body.to_string().into_bytes(),
+        };
+        Ok(TransportResponse::new(status, body))
+    }
+}
+
+/// Drops events before the `eventID` of `path` from a recorded event list.
+fn since_event(body: &Value, path: &str) -> Value {
+    let since = Url::parse(&format!("https://replay{}", path))
+        .ok()
+        .and_then(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == "eventID")
+                .and_then(|(_, value)| value.parse::<u64>().ok())
+        });
+    match (since, body["Events"].as_array()) {
+        (Some(since), Some(events)) => {
+            let events: Vec<&Value> = events
+                .iter()
+                .filter(|e| e["EventID"].as_u64().unwrap_or_default() >= since)
+                .collect();
+            json!({ "Events": events })
+        }
+        _ => body.clone(),
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn send<'a>(
+        &'a self,
+        request: &'a TransportRequest,
+    ) -> BoxFuture<'a, LiveClientResult<TransportResponse>> {
+        let response = self.response(&request.path);
+        Box::pin(async move { response })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::client::LiveClient;
+    use crate::live::mock::{MockGame, MockServer};
+    use crate::live::recorder::{Recorder, RecorderConfig};
+    use crate::live::transport::tests::client;
+    use futures::StreamExt;
+    use reqwest::Method;
+    use std::fs;
+
+    fn entry(elapsed: u64, poll: u64, path: &str, body: Value) -> RecordEntry {
+        RecordEntry {
+            timestamp: 0,
+            elapsed,
+            poll,
+            path: path.to_string(),
+            status: Some(200),
+            latency_ms: 1.0,
+            body,
+            text: None,
+            error: None,
+        }
+    }
+
+    fn stats(game_time: f64) -> Value {
+        json!({
+            "gameMode": "CLASSIC",
+            "gameTime": game_time,
+            "mapName": "Map11",
+            "mapNumber": 11,
+            "mapTerrain": "Default"
+        })
+    }
+
+    fn events(count: u64) -> Value {
+        let events: Vec<Value> = (0..count)
+            .map(|id| json!({ "EventID": id, "EventName": "MinionsSpawning", "EventTime": 65.0 }))
+            .collect();
+        json!({ "Events": events })
+    }
+
+    #[tokio::test]
+    async fn replay_recorded_session() {
+        let server = MockServer::start(MockGame::new()).await.unwrap();
+        let dir = std::env::temp_dir().join(format!("rumble-replay-{}", std::process::id()));
+        let path = dir.join("game.ndjson");
+        let config = RecorderConfig {
+            per_player: false,
+            max_entries_per_file: Some(5),
+            gzip: true,
+            ..RecorderConfig::new()
+        };
+        let lcu = LiveClient::new(server.config());
+        let mut recorder = Recorder::new(&lcu, &path, config);
+        recorder.poll().await.unwrap();
+        server.shutdown().await;
+        recorder.poll().await.unwrap();
+        recorder.finish().unwrap();
+
+        let replay = ReplayTransport::open(&path, Playback::Step).unwrap();
+        assert_eq!(replay.recording().polls.len(), 2);
+        let lcu = client(replay);
+        let stats = lcu.game_api().get_game_stats().await.unwrap();
+        assert_eq!(stats.game_mode, "CLASSIC");
+        let name = lcu.player_api().get_active_player_name().await.unwrap();
+        assert_eq!(name, "Riot Tuxedo");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn replay_step() {
+        let recording = Recording::new(vec![
+            entry(0, 0, "/liveclientdata/gamestats", stats(1.0)),
+            entry(5, 0, "/liveclientdata/eventdata", events(1)),
+            entry(1000, 1, "/liveclientdata/gamestats", stats(2.0)),
+            entry(1005, 1, "/liveclientdata/eventdata", events(3)),
+        ]);
+        let replay = ReplayTransport::new(recording, Playback::Step);
+        assert_eq!(replay.position(), Duration::from_millis(5));
+        let request = TransportRequest::new(Method::GET, "/liveclientdata/gamestats");
+
+        let response = replay.send(&request).await.unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&response.body).unwrap(),
+            stats(1.0)
+        );
+        assert!(replay.step());
+        assert!(replay.is_finished());
+        let response = replay.send(&request).await.unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&response.body).unwrap(),
+            stats(2.0)
+        );
+        assert!(!replay.step());
+
+        let request = TransportRequest::new(Method::GET, "/liveclientdata/activeplayer");
+        let err = replay.send(&request).await.unwrap_err();
+        assert!(err.is_connect());
+    }
+
+    #[tokio::test]
+    async fn replay_events_since() {
+        let recording = Recording::new(vec![entry(0, 0, "/liveclientdata/eventdata", events(3))]);
+        let lcu = client(ReplayTransport::new(recording, Playback::Step));
+        let events = lcu.game_api().get_game_events_since(0).await.unwrap();
+        assert_eq!(events.events.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn replay_accelerated() {
+        let mut closed = entry(2000, 2, "/liveclientdata/eventdata", Value::Null);
+        closed.status = None;
+        closed.error = Some("connection refused".to_string());
+        let recording = Recording::new(vec![
+            entry(0, 0, "/liveclientdata/eventdata", events(1)),
+            entry(1000, 1, "/liveclientdata/eventdata", events(3)),
+            closed,
+        ]);
+        let lcu = client(ReplayTransport::new(recording, Playback::Accelerated(10.0)));
+
+        let events: Vec<_> = lcu
+            .event_stream(Duration::from_millis(5))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|e| e.is_ok()));
+    }
+}
@@ -0,0 +1,96 @@
+//! The request behind each endpoint, shared by the async and blocking clients.
+
+use reqwest::Method;
+
+use crate::live::error::LiveClientResult;
+use crate::live::models::{PlaybackUpdate, PlayerId, RenderUpdate};
+use crate::live::sequence::Sequence;
+use crate::live::transport::TransportRequest;
+
+fn get(path: impl Into<String>) -> TransportRequest {
+    TransportRequest::new(Method::GET, path)
+}
+
+pub(crate) fn player_score(player: &PlayerId) -> TransportRequest {
+    get(player.path("/liveclientdata/playerscores"))
+}
+
+pub(crate) fn player_items(player: &PlayerId) -> TransportRequest {
+    get(player.path("/liveclientdata/playeritems"))
+}
+
+pub(crate) fn player_summoner_spells(player: &PlayerId) -> TransportRequest {
+    get(player.path("/liveclientdata/playersummonerspells"))
+}
+
+pub(crate) fn player_main_runes(player: &PlayerId) -> TransportRequest {
+    get(player.path("/liveclientdata/playermainrunes"))
+}
+
+pub(crate) fn player_list() -> TransportRequest {
+    get("/liveclientdata/playerlist")
+}
+
+pub(crate) fn active_player_abilities() -> TransportRequest {
+    get("/liveclientdata/activeplayerabilities")
+}
+
+pub(crate) fn active_player_runes() -> TransportRequest {
+    get("/liveclientdata/activeplayerrunes")
+}
+
+pub(crate) fn active_player() -> TransportRequest {
+    get("/liveclientdata/activeplayer")
+}
+
+pub(crate) fn active_player_name() -> TransportRequest {
+    get("/liveclientdata/activeplayername")
+}
+
+pub(crate) fn all_game_data() -> TransportRequest {
+    get("/liveclientdata/allgamedata")
+}
+
+pub(crate) fn game_stats() -> TransportRequest {
+    get("/liveclientdata/gamestats")
+}
+
+pub(crate) fn game_events() -> TransportRequest {
+    get("/liveclientdata/eventdata")
+}
+
+pub(crate) fn game_events_since(event_id: i32) -> TransportRequest {
+    // eventID is the id of the next event we expect to see
+    get(format!(
+        "/liveclientdata/eventdata?eventID={}",
+        event_id + 1
+    ))
+}
+
+pub(crate) fn replay_game() -> TransportRequest {
+    get("/replay/game")
+}
+
+pub(crate) fn playback() -> TransportRequest {
+    get("/replay/playback")
+}
+
+pub(crate) fn set_playback(update: &PlaybackUpdate) -> LiveClientResult<TransportRequest> {
+    TransportRequest::new(Method::POST, "/replay/playback").json(update)
+}
+
+pub(crate) fn render() -> TransportRequest {
+    get("/replay/render")
+}
+
+pub(crate) fn update_render(update: &RenderUpdate) -> LiveClientResult<TransportRequest> {
+    TransportRequest::new(Method::POST, "/replay/render").json(update)
+}
+
+pub(crate) fn sequence() -> TransportRequest {
+    get("/replay/sequence")
+}
+
+pub(crate) fn set_sequence(sequence: &Sequence) -> LiveClientResult<TransportRequest> {
+    TransportRequest::new(Method::POST, "/replay/sequence").json(sequence)
+}
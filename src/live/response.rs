@@ -1,6 +1,6 @@
-use reqwest::Response;
+use crate::live::transport::TransportResponse;
 
 pub struct ResponseInfo {
-    pub response: Response,
+    pub response: TransportResponse,
     pub retries: u8,
 }
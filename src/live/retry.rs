@@ -0,0 +1,284 @@
+#[cfg(any(feature = "async", feature = "blocking"))]
+use std::collections::hash_map::RandomState;
+use std::fmt;
+#[cfg(any(feature = "async", feature = "blocking"))]
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(any(feature = "async", feature = "blocking"))]
+use std::time::Instant;
+
+use reqwest::StatusCode;
+
+use crate::live::error::LiveClientError;
+#[cfg(any(feature = "async", feature = "blocking"))]
+use crate::live::error::LiveClientResult;
+#[cfg(any(feature = "async", feature = "blocking"))]
+use crate::live::response::ResponseInfo;
+#[cfg(any(feature = "async", feature = "blocking"))]
+use crate::live::transport::TransportResponse;
+
+pub type RetryPredicate = Arc<dyn Fn(&LiveClientError) -> bool + Send + Sync>;
+
+/// Decides whether and when a failed request is retried.
+///
+/// Delays grow exponentially from `initial_backoff` up to `max_backoff`, with up to
+/// `jitter` of each delay randomized away so pollers do not retry in lockstep.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u8,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    /// Fraction of each delay that is randomized, between 0.0 and 1.0.
+    pub jitter: f64,
+    /// Gives up once this much time has passed since the first attempt.
+    pub max_elapsed: Option<Duration>,
+    retryable: RetryPredicate,
+}
+
+impl RetryPolicy {
+    pub const DEFAULT_RETRIES: u8 = 3;
+    pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+    pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(2);
+    pub const DEFAULT_MULTIPLIER: f64 = 2.0;
+    pub const DEFAULT_JITTER: f64 = 0.5;
+
+    pub fn new() -> Self {
+        Self {
+            max_retries: Self::DEFAULT_RETRIES,
+            initial_backoff: Self::DEFAULT_INITIAL_BACKOFF,
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+            multiplier: Self::DEFAULT_MULTIPLIER,
+            jitter: Self::DEFAULT_JITTER,
+            max_elapsed: None,
+            retryable: Arc::new(Self::default_retryable),
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::new()
+        }
+    }
+
+    /// Replaces the predicate deciding which errors are worth retrying.
+    pub fn with_retryable(
+        mut self,
+        retryable: impl Fn(&LiveClientError) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.retryable = Arc::new(retryable);
+        self
+    }
+
+    /// Retries transport failures, timeouts and statuses the client may recover from.
+    pub fn default_retryable(error: &LiveClientError) -> bool {
+        match error {
+            LiveClientError::Connection { .. }
+            | LiveClientError::Disconnected { .. }
+            | LiveClientError::Timeout { .. } => true,
+            LiveClientError::Status { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::REQUEST_TIMEOUT
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_retryable(&self, error: &LiveClientError) -> bool {
+        (self.retryable)(error)
+    }
+
+    /// Delay before the retry following `retries` previous retries, without jitter.
+    pub fn backoff(&self, retries: u8) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(retries as i32);
+        self.initial_backoff.mul_f64(factor).min(self.max_backoff)
+    }
+
+    /// Returns how long to wait before retrying, or `None` to give up.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn next_delay(
+        &self,
+        retries: u8,
+        elapsed: Duration,
+        error: &LiveClientError,
+    ) -> Option<Duration> {
+        if retries >= self.max_retries || !self.is_retryable(error) {
+            return None;
+        }
+        let jitter = self.jitter.clamp(0.0, 1.0) * random_fraction();
+        let delay = self.backoff(retries).mul_f64(1.0 - jitter);
+        match self.max_elapsed {
+            Some(max_elapsed) if elapsed + delay > max_elapsed => None,
+            _ => Some(delay),
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .field("max_elapsed", &self.max_elapsed)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The attempts at one request, shared by the async and blocking clients which only
+/// differ in how they send and sleep.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) struct Attempts<'a> {
+    policy: &'a RetryPolicy,
+    start: Instant,
+    retries: u8,
+}
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) enum Attempt {
+    Done(LiveClientResult<ResponseInfo>),
+    RetryAfter(Duration),
+}
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+impl<'a> Attempts<'a> {
+    pub(crate) fn new(policy: &'a RetryPolicy) -> Self {
+        Self {
+            policy,
+            start: Instant::now(),
+            retries: 0,
+        }
+    }
+
+    /// Checks the outcome of sending the request, deciding whether to retry it.
+    pub(crate) fn check(&mut self, sent: LiveClientResult<TransportResponse>) -> Attempt {
+        let error = match sent {
+            Ok(response) if response.status.is_success() => {
+                return Attempt::Done(Ok(ResponseInfo {
+                    response,
+                    retries: self.retries,
+                }));
+            }
+            Ok(response) => {
+                let body = String::from_utf8_lossy(&response.body).into_owned();
+                LiveClientError::from_status(response.status, body, self.retries)
+            }
+            Err(e) => e.with_retries(self.retries),
+        };
+
+        match self
+            .policy
+            .next_delay(self.retries, self.start.elapsed(), &error)
+        {
+            Some(delay) => {
+                self.retries += 1;
+                Attempt::RetryAfter(delay)
+            }
+            None => Attempt::Done(Err(error)),
+        }
+    }
+}
+
+// randomly keyed hasher, good enough for jitter without pulling in a rng
+#[cfg(any(feature = "async", feature = "blocking"))]
+fn random_fraction() -> f64 {
+    let hasher = RandomState::new().build_hasher();
+    hasher.finish() as f64 / u64::MAX as f64
+}
+
+#[cfg(all(test, any(feature = "async", feature = "blocking")))]
+mod tests {
+    use super::*;
+
+    fn status(status: StatusCode) -> LiveClientError {
+        LiveClientError::from_status(status, String::new(), 0)
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy::new();
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(10), RetryPolicy::DEFAULT_MAX_BACKOFF);
+    }
+
+    #[test]
+    fn next_delay() {
+        let policy = RetryPolicy::new();
+        let error = status(StatusCode::SERVICE_UNAVAILABLE);
+        for retries in 0..policy.max_retries {
+            let delay = policy.next_delay(retries, Duration::ZERO, &error).unwrap();
+            assert!(delay <= policy.backoff(retries));
+            assert!(delay >= policy.backoff(retries) / 2);
+        }
+        assert!(policy
+            .next_delay(policy.max_retries, Duration::ZERO, &error)
+            .is_none());
+    }
+
+    #[test]
+    fn next_delay_max_elapsed() {
+        let policy = RetryPolicy {
+            max_elapsed: Some(Duration::from_millis(150)),
+            jitter: 0.0,
+            ..RetryPolicy::new()
+        };
+        let error = status(StatusCode::SERVICE_UNAVAILABLE);
+        assert!(policy.next_delay(0, Duration::ZERO, &error).is_some());
+        assert!(policy
+            .next_delay(1, Duration::from_millis(100), &error)
+            .is_none());
+    }
+
+    #[test]
+    fn attempts() {
+        let policy = RetryPolicy {
+            max_retries: 1,
+            ..RetryPolicy::new()
+        };
+        let mut attempts = Attempts::new(&policy);
+        let unavailable = TransportResponse::new(StatusCode::SERVICE_UNAVAILABLE, "");
+        assert!(matches!(
+            attempts.check(Ok(unavailable.clone())),
+            Attempt::RetryAfter(_)
+        ));
+        match attempts.check(Ok(unavailable)) {
+            Attempt::Done(Err(e)) => assert_eq!(e.retries(), 1),
+            _ => panic!("Did not give up"),
+        }
+
+        let mut attempts = Attempts::new(&policy);
+        match attempts.check(Ok(TransportResponse::new(StatusCode::OK, "{}"))) {
+            Attempt::Done(Ok(rinfo)) => assert_eq!(rinfo.retries, 0),
+            _ => panic!("Did not succeed"),
+        }
+    }
+
+    #[test]
+    fn retryable() {
+        let policy = RetryPolicy::new();
+        assert!(policy.is_retryable(&status(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(policy.is_retryable(&status(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(!policy.is_retryable(&status(StatusCode::BAD_REQUEST)));
+        assert!(!policy.is_retryable(&status(StatusCode::NOT_FOUND)));
+
+        let policy = policy.with_retryable(|e| e.status() == Some(StatusCode::NOT_FOUND));
+        assert!(policy.is_retryable(&status(StatusCode::NOT_FOUND)));
+        assert!(RetryPolicy::none()
+            .next_delay(0, Duration::ZERO, &status(StatusCode::SERVICE_UNAVAILABLE))
+            .is_none());
+    }
+}
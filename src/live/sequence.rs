@@ -0,0 +1,403 @@
+//! Keyframed timelines for `/replay/sequence`, played back by the replay while it runs.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::live::error::{LiveClientError, LiveClientResult};
+use crate::live::models::{Easing, Vector3};
+
+/// A value the track takes at `time` seconds into the game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe<T> {
+    pub time: f64,
+    pub value: T,
+    /// Blending towards the next keyframe.
+    pub blend: Easing,
+}
+
+impl<T> Keyframe<T> {
+    pub fn new(time: f64, value: T, blend: Easing) -> Self {
+        Self { time, value, blend }
+    }
+}
+
+/// A sequence document, one keyframe track per setting. Empty tracks are left out.
+///
+/// Tracks without a typed field are kept in `extra`, by API name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sequence {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub camera_position: Vec<Keyframe<Vector3>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub camera_rotation: Vec<Keyframe<Vector3>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub playback_speed: Vec<Keyframe<f64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field_of_view: Vec<Keyframe<f64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selection_name: Vec<Keyframe<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depth_of_field_enabled: Vec<Keyframe<bool>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depth_of_field_near: Vec<Keyframe<f64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depth_of_field_mid: Vec<Keyframe<f64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depth_of_field_far: Vec<Keyframe<f64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depth_of_field_width: Vec<Keyframe<f64>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Sequence {
+    pub fn builder() -> SequenceBuilder {
+        SequenceBuilder::new()
+    }
+
+    /// Time of the last keyframe.
+    pub fn duration(&self) -> f64 {
+        self.keyframe_times()
+            .into_iter()
+            .flat_map(|(_, times)| times)
+            .fold(0.0, f64::max)
+    }
+
+    /// Checks that every track has finite, non-negative, strictly increasing times and
+    /// that values are in range, e.g. a field of view between 0 and 180 degrees.
+    pub fn validate(&self) -> LiveClientResult<()> {
+        for (track, times) in self.keyframe_times() {
+            if let Some(time) = times.iter().find(|t| !t.is_finite() || **t < 0.0) {
+                return Err(invalid(track, format!("invalid keyframe time {}", time)));
+            }
+            if let Some(pair) = times.windows(2).find(|pair| pair[0] >= pair[1]) {
+                return Err(invalid(
+                    track,
+                    format!("keyframe at {} follows {}", pair[1], pair[0]),
+                ));
+            }
+        }
+
+        let vectors = [
+            ("cameraPosition", &self.camera_position),
+            ("cameraRotation", &self.camera_rotation),
+        ];
+        for (track, keyframes) in vectors {
+            if let Some(k) = keyframes.iter().find(|k| {
+                !(k.value.x.is_finite() && k.value.y.is_finite() && k.value.z.is_finite())
+            }) {
+                return Err(invalid(track, format!("invalid value at {}", k.time)));
+            }
+        }
+        let ranges = [
+            ("playbackSpeed", &self.playback_speed, 0.0, f64::MAX),
+            ("fieldOfView", &self.field_of_view, f64::MIN_POSITIVE, 180.0),
+            ("depthOfFieldNear", &self.depth_of_field_near, 0.0, f64::MAX),
+            ("depthOfFieldMid", &self.depth_of_field_mid, 0.0, f64::MAX),
+            ("depthOfFieldFar", &self.depth_of_field_far, 0.0, f64::MAX),
+            (
+                "depthOfFieldWidth",
+                &self.depth_of_field_width,
+                0.0,
+                f64::MAX,
+            ),
+        ];
+        for (track, keyframes, min, max) in ranges {
+            if let Some(k) = keyframes
+                .iter()
+                .find(|k| !(k.value >= min && k.value <= max))
+            {
+                return Err(invalid(
+                    track,
+                    format!("value {} at {} is out of range", k.value, k.time),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses and validates a sequence document.
+    pub fn from_json(json: &str) -> LiveClientResult<Self> {
+        let sequence: Sequence = LiveClientError::decode(json.as_bytes(), 0)?;
+        sequence.validate()?;
+        Ok(sequence)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Failed to serialize Sequence.")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Self::from_json(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+
+    /// Keyframe times of every track, by API name.
+    fn keyframe_times(&self) -> Vec<(&str, Vec<f64>)> {
+        fn times<T>(keyframes: &[Keyframe<T>]) -> Vec<f64> {
+            keyframes.iter().map(|k| k.time).collect()
+        }
+
+        let mut tracks = vec![
+            ("cameraPosition", times(&self.camera_position)),
+            ("cameraRotation", times(&self.camera_rotation)),
+            ("playbackSpeed", times(&self.playback_speed)),
+            ("fieldOfView", times(&self.field_of_view)),
+            ("selectionName", times(&self.selection_name)),
+            ("depthOfFieldEnabled", times(&self.depth_of_field_enabled)),
+            ("depthOfFieldNear", times(&self.depth_of_field_near)),
+            ("depthOfFieldMid", times(&self.depth_of_field_mid)),
+            ("depthOfFieldFar", times(&self.depth_of_field_far)),
+            ("depthOfFieldWidth", times(&self.depth_of_field_width)),
+        ];
+        for (track, keyframes) in &self.extra {
+            let times = keyframes
+                .as_array()
+                .map(|keyframes| {
+                    keyframes
+                        .iter()
+                        .map(|k| k["time"].as_f64().unwrap_or(f64::NAN))
+                        .collect()
+                })
+                .unwrap_or_else(|| vec![f64::NAN]);
+            tracks.push((track.as_str(), times));
+        }
+        tracks
+    }
+}
+
+fn invalid(track: &str, message: String) -> LiveClientError {
+    LiveClientError::InvalidRequest {
+        message: format!("sequence track {}: {}", track, message),
+    }
+}
+
+/// Builds a [`Sequence`] from keyframes added in any order.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceBuilder {
+    sequence: Sequence,
+}
+
+impl SequenceBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn camera_position(mut self, time: f64, position: Vector3, blend: Easing) -> Self {
+        self.sequence
+            .camera_position
+            .push(Keyframe::new(time, position, blend));
+        self
+    }
+
+    /// Yaw, pitch and roll in degrees.
+    pub fn camera_rotation(mut self, time: f64, rotation: Vector3, blend: Easing) -> Self {
+        self.sequence
+            .camera_rotation
+            .push(Keyframe::new(time, rotation, blend));
+        self
+    }
+
+    pub fn playback_speed(mut self, time: f64, speed: f64, blend: Easing) -> Self {
+        self.sequence
+            .playback_speed
+            .push(Keyframe::new(time, speed, blend));
+        self
+    }
+
+    pub fn field_of_view(mut self, time: f64, field_of_view: f64, blend: Easing) -> Self {
+        self.sequence
+            .field_of_view
+            .push(Keyframe::new(time, field_of_view, blend));
+        self
+    }
+
+    /// Selects the unit named `name`, usually with [`Easing::Snap`].
+    pub fn selection_name(mut self, time: f64, name: impl Into<String>, blend: Easing) -> Self {
+        self.sequence
+            .selection_name
+            .push(Keyframe::new(time, name.into(), blend));
+        self
+    }
+
+    /// Enables depth of field and focuses it at `near`, `mid` and `far`.
+    pub fn depth_of_field(
+        mut self,
+        time: f64,
+        near: f64,
+        mid: f64,
+        far: f64,
+        blend: Easing,
+    ) -> Self {
+        let sequence = &mut self.sequence;
+        sequence
+            .depth_of_field_enabled
+            .push(Keyframe::new(time, true, Easing::Snap));
+        sequence
+            .depth_of_field_near
+            .push(Keyframe::new(time, near, blend.clone()));
+        sequence
+            .depth_of_field_mid
+            .push(Keyframe::new(time, mid, blend.clone()));
+        sequence
+            .depth_of_field_far
+            .push(Keyframe::new(time, far, blend));
+        self
+    }
+
+    /// Adds a keyframe to any track by its API name, e.g. `skyboxRotation`.
+    pub fn keyframe(
+        mut self,
+        track: impl Into<String>,
+        time: f64,
+        value: impl Into<Value>,
+        blend: Easing,
+    ) -> Self {
+        let keyframe = Keyframe::new(time, value.into(), blend);
+        let keyframes = self
+            .sequence
+            .extra
+            .entry(track.into())
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(keyframes) = keyframes {
+            keyframes.push(serde_json::to_value(keyframe).expect("Failed to serialize Keyframe."));
+        }
+        self
+    }
+
+    /// Sorts every track by time and validates the sequence.
+    pub fn build(self) -> LiveClientResult<Sequence> {
+        fn sort<T>(keyframes: &mut [Keyframe<T>]) {
+            keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        }
+
+        let mut sequence = self.sequence;
+        sort(&mut sequence.camera_position);
+        sort(&mut sequence.camera_rotation);
+        sort(&mut sequence.playback_speed);
+        sort(&mut sequence.field_of_view);
+        sort(&mut sequence.selection_name);
+        sort(&mut sequence.depth_of_field_enabled);
+        sort(&mut sequence.depth_of_field_near);
+        sort(&mut sequence.depth_of_field_mid);
+        sort(&mut sequence.depth_of_field_far);
+        sort(&mut sequence.depth_of_field_width);
+        for keyframes in sequence.extra.values_mut() {
+            if let Value::Array(keyframes) = keyframes {
+                keyframes.sort_by(|a, b| {
+                    let time = |k: &Value| k["time"].as_f64().unwrap_or(f64::NAN);
+                    time(a).total_cmp(&time(b))
+                });
+            }
+        }
+        sequence.validate()?;
+        Ok(sequence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sequence() -> Sequence {
+        Sequence::builder()
+            .camera_position(
+                10.0,
+                Vector3::new(1000.0, 2000.0, 3000.0),
+                Easing::SmoothStep,
+            )
+            .camera_position(0.0, Vector3::new(0.0, 2000.0, 0.0), Easing::Linear)
+            .playback_speed(0.0, 0.5, Easing::Snap)
+            .selection_name(4.0, "Annie", Easing::Snap)
+            .keyframe("skyboxRotation", 2.0, 90.0, Easing::CubicEaseInOut)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn build() {
+        let sequence = sequence();
+        assert_eq!(sequence.camera_position[0].time, 0.0);
+        assert_eq!(sequence.duration(), 10.0);
+
+        let value = serde_json::to_value(&sequence).unwrap();
+        assert_eq!(
+            value["cameraPosition"][1],
+            json!({"time": 10.0, "value": {"x": 1000.0, "y": 2000.0, "z": 3000.0}, "blend": "smoothStep"})
+        );
+        assert_eq!(value["skyboxRotation"][0]["blend"], "cubicEaseInOut");
+        assert!(value.get("fieldOfView").is_none());
+    }
+
+    #[test]
+    fn validate() {
+        let err = Sequence::builder()
+            .field_of_view(0.0, 45.0, Easing::Linear)
+            .field_of_view(0.0, 60.0, Easing::Linear)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("fieldOfView"));
+
+        let err = Sequence::builder()
+            .field_of_view(1.0, 200.0, Easing::Linear)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+
+        let err = Sequence::builder()
+            .keyframe("skyboxRotation", -1.0, 0.0, Easing::Linear)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, LiveClientError::InvalidRequest { .. }));
+        assert!(Sequence::default().validate().is_ok());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let sequence = sequence();
+        assert_eq!(Sequence::from_json(&sequence.to_json()).unwrap(), sequence);
+
+        let json = r#"{
+            "playbackSpeed": [
+                {"time": 0.0, "value": 1.0, "blend": "futureEaseIn"},
+                {"time": 3.0, "value": 1.0, "blend": "linear"}
+            ]
+        }"#;
+        let sequence = Sequence::from_json(json).unwrap();
+        assert_eq!(
+            sequence.playback_speed[0].blend,
+            Easing::Other("futureEaseIn".to_string())
+        );
+        assert!(Sequence::from_json(
+            r#"{"playbackSpeed": [{"time": 0.0, "value": -1.0, "blend": "linear"}]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn load_and_save() {
+        let path =
+            std::env::temp_dir().join(format!("rumble-sequence-{}.json", std::process::id()));
+        let sequence = sequence();
+        sequence.save(&path).unwrap();
+        assert_eq!(Sequence::load(&path).unwrap(), sequence);
+
+        fs::write(
+            &path,
+            r#"{"fieldOfView": [{"time": 0.0, "value": 0.0, "blend": "linear"}]}"#,
+        )
+        .unwrap();
+        let err = Sequence::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(feature = "async")]
+use std::time::Duration;
+
+use serde_json::Value;
+
+#[cfg(feature = "async")]
+use crate::live::client::LiveClient;
+use crate::live::error::{LiveClientError, LiveClientResult};
+#[cfg(feature = "async")]
+use crate::live::request;
+use crate::live::response::ResponseInfo;
+#[cfg(feature = "async")]
+use crate::live::retry::RetryPolicy;
+
+/// Lifecycle of the game as seen through the Live Client Data API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    /// Nothing is listening on the API port.
+    NotRunning,
+    /// The client answers but the game has not started yet.
+    Loading,
+    InProgress,
+    /// The game is over and the client is showing the end screen.
+    Ended,
+}
+
+impl GameState {
+    fn from_events(events: &Value) -> Self {
+        let names: Vec<&str> = events["Events"]
+            .as_array()
+            .map(|events| {
+                events
+                    .iter()
+                    .filter_map(|e| e["EventName"].as_str())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if names.contains(&"GameEnd") {
+            GameState::Ended
+        } else if names.contains(&"GameStart") {
+            GameState::InProgress
+        } else {
+            GameState::Loading
+        }
+    }
+
+    /// Classifies the answer to a request for the event data.
+    pub(crate) fn from_response(
+        response: LiveClientResult<ResponseInfo>,
+    ) -> LiveClientResult<Self> {
+        // events are read untyped so an unmodeled event cannot hide the state
+        let events = response.and_then(|rinfo| {
+            LiveClientError::decode::<Value>(&rinfo.response.body, rinfo.retries)
+        });
+        match events {
+            Ok(events) => Ok(GameState::from_events(&events)),
+            Err(e) if e.is_connect() => Ok(GameState::NotRunning),
+            Err(e) if e.status().is_some() => Ok(GameState::Loading),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl LiveClient {
+    /// Classifies the local game client into a [`GameState`].
+    ///
+    /// Only errors that cannot be attributed to a lifecycle state are returned.
+    /// Requests are never retried, the state is read as the client reports it.
+    pub async fn game_state(&self) -> LiveClientResult<GameState> {
+        let response = self
+            .execute_raw_with_policy(request::game_events(), &RetryPolicy::none())
+            .await;
+        GameState::from_response(response)
+    }
+
+    /// Waits until a game is in progress, checking every `interval`.
+    pub async fn wait_for_game(&self, interval: Duration) -> LiveClientResult<()> {
+        loop {
+            if self.game_state().await? == GameState::InProgress {
+                return Ok(());
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use super::*;
+    use crate::live::mock::{MockGame, MockServer};
+
+    #[test]
+    fn state_from_events() {
+        let loading: Value = serde_json::from_str(r#"{"Events": []}"#).unwrap();
+        assert_eq!(GameState::from_events(&loading), GameState::Loading);
+
+        let in_progress: Value = serde_json::from_str(
+            r#"{"Events": [{"EventID": 0, "EventName": "GameStart", "EventTime": 0}]}"#,
+        )
+        .unwrap();
+        assert_eq!(GameState::from_events(&in_progress), GameState::InProgress);
+
+        let ended: Value = serde_json::from_str(
+            r#"{
+                "Events": [
+                    {"EventID": 0, "EventName": "GameStart", "EventTime": 0},
+                    {"EventID": 1, "EventName": "GameEnd", "EventTime": 1200, "Result": "Win"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(GameState::from_events(&ended), GameState::Ended);
+    }
+
+    #[tokio::test]
+    async fn game_state_not_running() {
+        let mut config = crate::live::config::LiveClientConfig::new();
+        config.base_url = "https://127.0.0.1:1".into();
+        let lcu = LiveClient::new(config);
+        assert_eq!(lcu.game_state().await.unwrap(), GameState::NotRunning);
+    }
+
+    #[tokio::test]
+    async fn wait_for_game() {
+        let game = MockGame::new().with_loading_time(Duration::from_millis(200));
+        let server = MockServer::start(game).await.unwrap();
+        let lcu = LiveClient::new(server.config());
+        assert_eq!(lcu.game_state().await.unwrap(), GameState::Loading);
+
+        lcu.wait_for_game(Duration::from_millis(20)).await.unwrap();
+        assert_eq!(lcu.game_state().await.unwrap(), GameState::InProgress);
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, Method, StatusCode};
+
+use crate::live::config::LiveClientConfig;
+use crate::live::error::{LiveClientError, LiveClientResult};
+
+/// A request to the game client, relative to the configured base url.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransportRequest {
+    pub method: Method,
+    /// Path and query, e.g. `/liveclientdata/playerscores?summonerName=Riot%20Tuxedo`.
+    pub path: String,
+    /// JSON body.
+    pub body: Option<Vec<u8>>,
+    /// Overrides the transport's default timeout.
+    pub timeout: Option<Duration>,
+}
+
+impl TransportRequest {
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            body: None,
+            timeout: None,
+        }
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Applies `timeout` if there is one, e.g. the override of an api.
+    pub fn with_optional_timeout(self, timeout: Option<Duration>) -> Self {
+        match timeout {
+            Some(timeout) => self.timeout(timeout),
+            None => self,
+        }
+    }
+
+    pub fn json<T: serde::Serialize + ?Sized>(mut self, body: &T) -> LiveClientResult<Self> {
+        let body = serde_json::to_vec(body).map_err(|e| LiveClientError::InvalidRequest {
+            message: format!("could not serialize request body: {}", e),
+        })?;
+        self.body = Some(body);
+        Ok(self)
+    }
+
+    /// Builds the reqwest request, starting from the client's `request` method.
+    pub(crate) fn build<B: HttpRequestBuilder>(
+        &self,
+        base_url: &str,
+        request: impl FnOnce(Method, String) -> B,
+    ) -> B {
+        let mut builder = request(self.method.clone(), format!("{}{}", base_url, self.path));
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(body) = &self.body {
+            builder = builder.json_body(body.clone());
+        }
+        builder
+    }
+}
+
+/// The settings of a [`TransportRequest`], on the async and blocking reqwest request builders.
+pub(crate) trait HttpRequestBuilder: Sized {
+    fn timeout(self, timeout: Duration) -> Self;
+    fn json_body(self, body: Vec<u8>) -> Self;
+}
+
+macro_rules! impl_http_request_builder {
+    ($builder:ty) => {
+        impl HttpRequestBuilder for $builder {
+            fn timeout(self, timeout: Duration) -> Self {
+                self.timeout(timeout)
+            }
+
+            fn json_body(self, body: Vec<u8>) -> Self {
+                self.header(CONTENT_TYPE, "application/json").body(body)
+            }
+        }
+    };
+}
+
+impl_http_request_builder!(reqwest::RequestBuilder);
+#[cfg(feature = "blocking")]
+impl_http_request_builder!(reqwest::blocking::RequestBuilder);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub body: Vec<u8>,
+}
+
+impl TransportResponse {
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+        }
+    }
+
+    /// The response once reqwest sent the request and read, or failed to read, the body.
+    pub(crate) fn read(
+        status: StatusCode,
+        body: reqwest::Result<impl AsRef<[u8]>>,
+    ) -> LiveClientResult<Self> {
+        let body = body.map_err(|e| LiveClientError::from_reqwest(e, 0))?;
+        Ok(Self::new(status, body.as_ref()))
+    }
+}
+
+/// Sends requests to the game client on behalf of [`LiveClient`](crate::live::client::LiveClient).
+///
+/// Transports only deliver requests, retries and decoding are handled by the client.
+/// Any status is a successful send, errors are reserved for failing to get a response.
+pub trait Transport: Send + Sync {
+    fn send<'a>(
+        &'a self,
+        request: &'a TransportRequest,
+    ) -> BoxFuture<'a, LiveClientResult<TransportResponse>>;
+}
+
+/// Shares a transport with the client, e.g. to inspect a [`MemoryTransport`] afterwards.
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send<'a>(
+        &'a self,
+        request: &'a TransportRequest,
+    ) -> BoxFuture<'a, LiveClientResult<TransportResponse>> {
+        (**self).send(request)
+    }
+}
+
+/// The default transport, talking HTTPS to the game client with reqwest.
+pub struct ReqwestTransport {
+    base_url: String,
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(config: &LiveClientConfig) -> LiveClientResult<Self> {
+        let client = config
+            .configure(Client::builder())?
+            .build()
+            .map_err(|e| LiveClientError::from_reqwest(e, 0))?;
+        Ok(Self {
+            base_url: config.base_url.clone(),
+            client,
+        })
+    }
+
+    async fn send_request(
+        &self,
+        request: &TransportRequest,
+    ) -> LiveClientResult<TransportResponse> {
+        let response = request
+            .build(&self.base_url, |method, url| {
+                self.client.request(method, url)
+            })
+            .send()
+            .await
+            .map_err(|e| LiveClientError::from_reqwest(e, 0))?;
+        TransportResponse::read(response.status(), response.bytes().await)
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send<'a>(
+        &'a self,
+        request: &'a TransportRequest,
+    ) -> BoxFuture<'a, LiveClientResult<TransportResponse>> {
+        Box::pin(self.send_request(request))
+    }
+}
+
+/// Serves canned responses from memory, for testing code built on the endpoint apis.
+///
+/// Responses are looked up by path and query first, then by path alone. Unknown paths
+/// get the same 404 the game client answers with.
+#[derive(Default)]
+pub struct MemoryTransport {
+    responses: Mutex<HashMap<String, TransportResponse>>,
+    requests: Mutex<Vec<TransportRequest>>,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves `json` with a 200 status for `path`.
+    pub fn respond(&self, path: impl Into<String>, json: impl Into<Vec<u8>>) {
+        self.respond_with(path, TransportResponse::new(StatusCode::OK, json));
+    }
+
+    pub fn respond_with(&self, path: impl Into<String>, response: TransportResponse) {
+        self.responses.lock().unwrap().insert(path.into(), response);
+    }
+
+    /// Builder style [`MemoryTransport::respond`].
+    pub fn with_response(self, path: impl Into<String>, json: impl Into<Vec<u8>>) -> Self {
+        self.respond(path, json);
+        self
+    }
+
+    /// Requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn response(&self, path: &str) -> TransportResponse {
+        let responses = self.responses.lock().unwrap();
+        let without_query = path.split('?').next().unwrap_or(path);
+        responses
+            .get(path)
+            .or_else(|| responses.get(without_query))
+            .cloned()
+            .unwrap_or_else(|| {
+                TransportResponse::new(
+                    StatusCode::NOT_FOUND,
+                    format!(
+                        r#"{{"errorCode": "RESOURCE_NOT_FOUND", "httpStatus": 404, "message": "No route for {}"}}"#,
+                        without_query
+                    ),
+                )
+            })
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn send<'a>(
+        &'a self,
+        request: &'a TransportRequest,
+    ) -> BoxFuture<'a, LiveClientResult<TransportResponse>> {
+        self.requests.lock().unwrap().push(request.clone());
+        let response = self.response(&request.path);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+pub(crate) mod tests {
+    use super::*;
+    use crate::live::client::LiveClient;
+    use crate::live::models::GameStats;
+    use crate::live::retry::RetryPolicy;
+
+    /// A client on `transport` that does not retry, for the tests of every module.
+    pub(crate) fn client(transport: impl Transport + 'static) -> LiveClient {
+        let mut config = LiveClientConfig::new();
+        config.retry_policy = RetryPolicy::none();
+        LiveClient::with_transport(config, transport)
+    }
+
+    #[tokio::test]
+    async fn memory_transport() {
+        let transport = MemoryTransport::new().with_response(
+            "/liveclientdata/gamestats",
+            r#"{
+                "gameMode": "CLASSIC",
+                "gameTime": 12.5,
+                "mapName": "Map11",
+                "mapNumber": 11,
+                "mapTerrain": "Default"
+            }"#,
+        );
+        let lcu = client(transport);
+
+        let stats: GameStats = lcu.game_api().get_game_stats().await.unwrap();
+        assert_eq!(stats.game_time, 12.5);
+    }
+
+    #[tokio::test]
+    async fn memory_transport_query_fallback() {
+        let transport = MemoryTransport::new()
+            .with_response("/liveclientdata/eventdata", r#"{"Events": []}"#)
+            .with_response("/liveclientdata/activeplayername", r#""Riot Tuxedo""#);
+        let lcu = client(transport);
+
+        let events = lcu.game_api().get_game_events_since(3).await.unwrap();
+        assert!(events.events.is_empty());
+        let name = lcu.player_api().get_active_player_name().await.unwrap();
+        assert_eq!(name, "Riot Tuxedo");
+    }
+
+    #[tokio::test]
+    async fn memory_transport_not_found() {
+        let lcu = client(MemoryTransport::new());
+        let err = lcu.game_api().get_game_stats().await.unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+    }
+}